@@ -0,0 +1,179 @@
+#version 460
+#extension GL_EXT_buffer_reference : require
+#extension GL_EXT_shader_explicit_arithmetic_types_int64 : require
+
+// --- GAUSSIENNES 3DGS ---
+// Une instance par gaussienne, dans l'ordre trié par splat_sort.comp.
+// La covariance 3D (R S S^T R^T) est projetée en 2D (jacobienne de la
+// perspective), puis un quad aligné sur ses axes propres couvre 3 écarts-types.
+
+layout(buffer_reference, std430) readonly buffer Geometry { float data[]; };
+layout(buffer_reference, std430) readonly buffer QuantizedGeometry {
+    vec4 bounds_min; vec4 bounds_max; uint data[];
+};
+layout(buffer_reference, std430) readonly buffer FloatStream { float data[]; };
+layout(buffer_reference, std430) readonly buffer Order { uint data[]; };
+// Flux ATTR splat_* (cf. seed_architect::splat)
+layout(buffer_reference, std430) readonly buffer SplatAttributes {
+    uint64_t scale;    // float[3] : écarts-types
+    uint64_t rotation; // float[4] : quaternion xyzw
+    uint64_t opacity;  // float[1]
+    uint64_t sh;       // float[3 * sh_count] : RGB par coefficient
+};
+
+layout(push_constant) uniform Constants {
+    uint64_t geo_ptr;    // 0..8
+    uint64_t attr_ptr;   // 8..16 (SplatAttributes)
+    uint64_t order_ptr;  // 16..24 (indices du plus lointain au plus proche)
+    uint atom_layout;    // 24..28 (0 = float[6], 1 = quantifié)
+    uint sh_count;       // 28..32 (coefficients par canal : 1, 4, 9 ou 16)
+    layout(offset = 32) vec3 cam_pos;    // 32..44
+    layout(offset = 48) vec2 focal;      // 48..56 (pixels)
+    vec2 viewport;                       // 56..64 (pixels)
+    layout(offset = 64) mat4 view;       // 64..128
+    layout(offset = 128) mat4 view_proj; // 128..192
+} pc;
+
+// --- VERTEX SHADER ---
+#ifdef VERTEX_SHADER
+layout(location = 0) out vec4 vColor;  // RGB + opacité
+layout(location = 1) out vec2 vOffset; // Position dans la gaussienne, en écarts-types
+
+// Deux triangles par quad
+const vec2 CORNERS[6] = vec2[](vec2(-1, -1), vec2(1, -1), vec2(1, 1), vec2(-1, -1), vec2(1, 1), vec2(-1, 1));
+
+// Constantes des harmoniques sphériques (mêmes conventions que l'entraînement 3DGS)
+const float SH_C0 = 0.28209479177387814;
+const float SH_C1 = 0.4886025119029199;
+const float SH_C2[5] = float[](1.0925484305920792, -1.0925484305920792, 0.31539156525252005, -1.0925484305920792, 0.5462742152960396);
+const float SH_C3[7] = float[](-0.5900435899266435, 2.890611442640554, -0.4570457994644658, 0.3731763325901154, -0.4570457994644658, 1.445305721320277, -0.5900435899266435);
+
+vec3 atomPosition(uint i) {
+    if (pc.atom_layout == 1) {
+        QuantizedGeometry qgeo = QuantizedGeometry(pc.geo_ptr);
+        uint xy = qgeo.data[i * 3];
+        vec3 q = vec3(xy & 0xFFFFu, xy >> 16, qgeo.data[i * 3 + 1] & 0xFFFFu) / 65535.0;
+        return mix(qgeo.bounds_min.xyz, qgeo.bounds_max.xyz, q);
+    }
+    Geometry geo = Geometry(pc.geo_ptr);
+    uint base = i * 6;
+    return vec3(geo.data[base], geo.data[base+1], geo.data[base+2]);
+}
+
+vec3 shCoeff(FloatStream sh, uint atom, uint k) {
+    uint b = (atom * pc.sh_count + k) * 3;
+    return vec3(sh.data[b], sh.data[b+1], sh.data[b+2]);
+}
+
+// Couleur vue dans la direction `dir` (caméra -> gaussienne)
+vec3 evalSH(uint atom, vec3 dir) {
+    FloatStream sh = FloatStream(SplatAttributes(pc.attr_ptr).sh);
+    vec3 c = SH_C0 * shCoeff(sh, atom, 0);
+    if (pc.sh_count > 1) {
+        float x = dir.x, y = dir.y, z = dir.z;
+        c += SH_C1 * (-y * shCoeff(sh, atom, 1) + z * shCoeff(sh, atom, 2) - x * shCoeff(sh, atom, 3));
+        if (pc.sh_count > 4) {
+            float xx = x * x, yy = y * y, zz = z * z;
+            c += SH_C2[0] * x * y * shCoeff(sh, atom, 4)
+               + SH_C2[1] * y * z * shCoeff(sh, atom, 5)
+               + SH_C2[2] * (2.0 * zz - xx - yy) * shCoeff(sh, atom, 6)
+               + SH_C2[3] * x * z * shCoeff(sh, atom, 7)
+               + SH_C2[4] * (xx - yy) * shCoeff(sh, atom, 8);
+            if (pc.sh_count > 9) {
+                c += SH_C3[0] * y * (3.0 * xx - yy) * shCoeff(sh, atom, 9)
+                   + SH_C3[1] * x * y * z * shCoeff(sh, atom, 10)
+                   + SH_C3[2] * y * (4.0 * zz - xx - yy) * shCoeff(sh, atom, 11)
+                   + SH_C3[3] * z * (2.0 * zz - 3.0 * xx - 3.0 * yy) * shCoeff(sh, atom, 12)
+                   + SH_C3[4] * x * (4.0 * zz - xx - yy) * shCoeff(sh, atom, 13)
+                   + SH_C3[5] * z * (xx - yy) * shCoeff(sh, atom, 14)
+                   + SH_C3[6] * x * (xx - 3.0 * yy) * shCoeff(sh, atom, 15);
+            }
+        }
+    }
+    return max(c + 0.5, 0.0);
+}
+
+mat3 quatToMat(vec4 q) {
+    float x = q.x, y = q.y, z = q.z, w = q.w;
+    return mat3(
+        1.0 - 2.0 * (y * y + z * z), 2.0 * (x * y + w * z), 2.0 * (x * z - w * y),
+        2.0 * (x * y - w * z), 1.0 - 2.0 * (x * x + z * z), 2.0 * (y * z + w * x),
+        2.0 * (x * z + w * y), 2.0 * (y * z - w * x), 1.0 - 2.0 * (x * x + y * y)
+    );
+}
+
+void main() {
+    uint atom = Order(pc.order_ptr).data[gl_InstanceIndex];
+    vec3 pos = atomPosition(atom);
+    vec4 view_pos = pc.view * vec4(pos, 1.0);
+    vec4 clip = pc.view_proj * vec4(pos, 1.0);
+    float depth = -view_pos.z;
+
+    // Derrière la caméra ou loin hors champ : quad dégénéré hors du volume de clip
+    vOffset = vec2(0.0);
+    vColor = vec4(0.0);
+    if (depth < 0.1 || any(greaterThan(abs(clip.xy), vec2(1.3 * clip.w)))) {
+        gl_Position = vec4(0.0, 0.0, 2.0, 1.0);
+        return;
+    }
+
+    SplatAttributes attrs = SplatAttributes(pc.attr_ptr);
+    FloatStream scales = FloatStream(attrs.scale);
+    FloatStream rotations = FloatStream(attrs.rotation);
+    vec3 s = vec3(scales.data[atom * 3], scales.data[atom * 3 + 1], scales.data[atom * 3 + 2]);
+    vec4 q = vec4(rotations.data[atom * 4], rotations.data[atom * 4 + 1], rotations.data[atom * 4 + 2], rotations.data[atom * 4 + 3]);
+
+    // Covariance 3D monde
+    mat3 M = quatToMat(q) * mat3(s.x, 0.0, 0.0, 0.0, s.y, 0.0, 0.0, 0.0, s.z);
+    mat3 sigma = M * transpose(M);
+
+    // Jacobienne de la projection au centre (pixels), x/z borné comme à l'entraînement
+    vec2 limit = 1.3 * 0.5 * pc.viewport / pc.focal;
+    vec2 t = clamp(view_pos.xy / depth, -limit, limit) * depth;
+    mat3 J = mat3(
+        pc.focal.x / depth, 0.0, 0.0,
+        0.0, pc.focal.y / depth, 0.0,
+        pc.focal.x * t.x / (depth * depth), pc.focal.y * t.y / (depth * depth), 0.0
+    );
+    mat3 T = J * mat3(pc.view);
+    mat3 cov = T * sigma * transpose(T);
+
+    // Filtre passe-bas : au moins ~1 pixel de large
+    float a = cov[0][0] + 0.3;
+    float b = cov[0][1];
+    float c = cov[1][1] + 0.3;
+
+    // Axes propres de la covariance 2D
+    float mid = 0.5 * (a + c);
+    float r = sqrt(max(0.25 * (a - c) * (a - c) + b * b, 0.0));
+    vec2 lambda = vec2(mid + r, max(mid - r, 0.1));
+    vec2 axis1 = abs(b) > 1e-6 ? normalize(vec2(b, lambda.x - a)) : (a >= c ? vec2(1.0, 0.0) : vec2(0.0, 1.0));
+    vec2 axis2 = vec2(-axis1.y, axis1.x);
+
+    // Quad à 3 écarts-types (plafonné à 1024 px par demi-axe)
+    vec2 sigma2d = sqrt(lambda);
+    vec2 radius = min(3.0 * sigma2d, vec2(1024.0));
+    vec2 corner = CORNERS[gl_VertexIndex];
+    vec2 pixel = corner.x * radius.x * axis1 + corner.y * radius.y * axis2;
+
+    gl_Position = clip;
+    gl_Position.xy += pixel * 2.0 / pc.viewport * clip.w;
+    vOffset = corner * radius / sigma2d;
+    vColor = vec4(evalSH(atom, normalize(pos - pc.cam_pos)), FloatStream(attrs.opacity).data[atom]);
+}
+#endif
+
+// --- FRAGMENT SHADER ---
+#ifdef FRAGMENT_SHADER
+layout(location = 0) in vec4 vColor;
+layout(location = 1) in vec2 vOffset;
+
+layout(location = 0) out vec4 outColor;
+
+void main() {
+    // Gaussienne dans son repère propre : exp(-d² / 2)
+    float alpha = min(0.99, vColor.a * exp(-0.5 * dot(vOffset, vOffset)));
+    if (alpha < 1.0 / 255.0) discard;
+    outColor = vec4(vColor.rgb, alpha);
+}
+#endif
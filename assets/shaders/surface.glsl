@@ -5,9 +5,24 @@
 // --- RESSOURCES PARTAGÉES ---
 
 layout(buffer_reference, std430) readonly buffer Geometry { float data[]; };
-layout(buffer_reference, std430) readonly buffer Material { 
-    vec3 base_color; float metallic; uint64_t emissive_ptr; 
-    float roughness; float ior; uvec2 _padding; 
+// Layout quantifié : AABB puis atomes de 3 uint (xy, z, normale octaédrique)
+layout(buffer_reference, std430) readonly buffer QuantizedGeometry {
+    vec4 bounds_min; vec4 bounds_max; uint data[];
+};
+// MaterialData (48 octets) : tableau indexé par l'attribut "material" de l'atome
+struct Material {
+    vec3 base_color; float metallic;
+    vec3 emissive; float roughness;
+    float ior; uint _padding[3];
+};
+layout(buffer_reference, std430) readonly buffer Materials { Material data[]; };
+layout(buffer_reference, std430) readonly buffer FloatStream { float data[]; };
+// Flux ATTR par atome présents dans le .seed (0 = absent)
+layout(buffer_reference, std430) readonly buffer AtomAttributes {
+    uint64_t material;  // float[1] : index matériau
+    uint64_t color;     // float[3] : map_Kd échantillonnée
+    uint64_t roughness; // float[1] : map_Pr échantillonnée
+    uint64_t metallic;  // float[1] : map_Pm échantillonnée
 };
 
 // Image pour stocker l'historique et lisser le rendu
@@ -17,11 +32,15 @@ layout(push_constant) uniform Constants {
     uint64_t geo_ptr;    // 0..8
     uint64_t mat_ptr;    // 8..16
     uint frame_index;    // 16..20
-    // Padding 20..32
+    uint atom_layout;    // 20..24 (0 = float[6], 1 = quantifié, 2 = frame animée float[3], 3 = float[6] position + vitesse)
+    uint64_t attr_ptr;   // 24..32 (AtomAttributes, 0 = aucun attribut)
     layout(offset = 32) vec3 cam_pos; // 32..44
     // Padding 44..48
     layout(offset = 48) mat4 model;      // 48..112
     layout(offset = 112) mat4 view_proj; // 112..176
+    uint64_t next_geo_ptr; // 176..184 (layouts 2/3 : frame suivante)
+    float anim_blend;      // 184..188 (position entre les deux frames, 0..1)
+    float anim_dt;         // 188..192 (secondes entre les frames, 0 = interpolation linéaire)
 } pc;
 
 // Générateur de Bruit Pseudo-Aléatoire (Gold Noise)
@@ -29,6 +48,14 @@ float random(vec2 st) {
     return fract(sin(dot(st.xy, vec2(12.9898,78.233))) * 43758.5453123);
 }
 
+// Normale octaédrique (snorm16 x2) -> vecteur unitaire
+vec3 octDecode(vec2 e) {
+    vec3 n = vec3(e, 1.0 - abs(e.x) - abs(e.y));
+    float t = max(-n.z, 0.0);
+    n.xy += vec2(n.x >= 0.0 ? -t : t, n.y >= 0.0 ? -t : t);
+    return normalize(n);
+}
+
 // --- VERTEX SHADER ---
 #ifdef VERTEX_SHADER
 layout(location = 0) out vec3 vColor;
@@ -36,13 +63,48 @@ layout(location = 1) out vec3 vNormal;
 layout(location = 2) out vec3 vWorldPos;
 layout(location = 3) out float vMetallic;
 layout(location = 4) out float vRoughness;
+layout(location = 5) out vec3 vEmissive;
+
+// Frames animées : lerp par atome (ids stables), Hermite cubique si les vitesses sont là
+vec3 animatedPosition(uint i) {
+    uint stride = pc.atom_layout == 3 ? 6 : 3;
+    Geometry a = Geometry(pc.geo_ptr);
+    Geometry b = Geometry(pc.next_geo_ptr);
+    uint base = i * stride;
+    vec3 p0 = vec3(a.data[base], a.data[base+1], a.data[base+2]);
+    vec3 p1 = vec3(b.data[base], b.data[base+1], b.data[base+2]);
+    float t = pc.anim_blend;
+    if (pc.atom_layout != 3 || pc.anim_dt <= 0.0) return mix(p0, p1, t);
+
+    // Tangentes = vitesses (unités/s) × durée d'une frame
+    vec3 m0 = vec3(a.data[base+3], a.data[base+4], a.data[base+5]) * pc.anim_dt;
+    vec3 m1 = vec3(b.data[base+3], b.data[base+4], b.data[base+5]) * pc.anim_dt;
+    float t2 = t * t;
+    float t3 = t2 * t;
+    return (2.0 * t3 - 3.0 * t2 + 1.0) * p0 + (t3 - 2.0 * t2 + t) * m0
+         + (3.0 * t2 - 2.0 * t3) * p1 + (t3 - t2) * m1;
+}
 
 void main() {
-    Geometry geo = Geometry(pc.geo_ptr);
-    uint base_idx = gl_VertexIndex * 6;
-    
-    vec3 pos = vec3(geo.data[base_idx], geo.data[base_idx+1], geo.data[base_idx+2]);
-    vec3 norm = vec3(geo.data[base_idx+3], geo.data[base_idx+4], geo.data[base_idx+5]);
+    vec3 pos;
+    vec3 norm;
+    if (pc.atom_layout == 1) {
+        QuantizedGeometry qgeo = QuantizedGeometry(pc.geo_ptr);
+        uint base_idx = gl_VertexIndex * 3;
+        uint xy = qgeo.data[base_idx];
+        vec3 q = vec3(xy & 0xFFFFu, xy >> 16, qgeo.data[base_idx+1] & 0xFFFFu) / 65535.0;
+        pos = mix(qgeo.bounds_min.xyz, qgeo.bounds_max.xyz, q);
+        norm = octDecode(unpackSnorm2x16(qgeo.data[base_idx+2]));
+    } else if (pc.atom_layout >= 2) {
+        // Cache animé : pas de normales, atome orienté vers la caméra
+        pos = animatedPosition(gl_VertexIndex);
+        norm = normalize(pc.cam_pos - pos);
+    } else {
+        Geometry geo = Geometry(pc.geo_ptr);
+        uint base_idx = gl_VertexIndex * 6;
+        pos = vec3(geo.data[base_idx], geo.data[base_idx+1], geo.data[base_idx+2]);
+        norm = vec3(geo.data[base_idx+3], geo.data[base_idx+4], geo.data[base_idx+5]);
+    }
     
     vec4 world_pos = pc.model * vec4(pos, 1.0);
     vWorldPos = world_pos.xyz;
@@ -63,14 +125,32 @@ gl_PointSize = (1400.0 / max(dist, 0.1)) * 0.008;
     
     // Extraction Matériau
     if (pc.mat_ptr != 0) {
-        Material mat = Material(pc.mat_ptr);
+        uint mat_index = 0;
+        if (pc.attr_ptr != 0 && AtomAttributes(pc.attr_ptr).material != 0) {
+            mat_index = uint(FloatStream(AtomAttributes(pc.attr_ptr).material).data[gl_VertexIndex]);
+        }
+        Material mat = Materials(pc.mat_ptr).data[mat_index];
         vColor = mat.base_color;
         vMetallic = mat.metallic;
         vRoughness = mat.roughness;
+        vEmissive = mat.emissive;
     } else {
-        vColor = vec3(1.0, 0.84, 0.0); // Gold defaut
+        vColor = vec3(1.0, 0.674, 0.0); // Gold defaut (linéaire)
         vMetallic = 1.0;
         vRoughness = 0.2;
+        vEmissive = vec3(0.0);
+    }
+
+    // Valeurs texturées par atome : prioritaires sur les constantes du matériau
+    if (pc.attr_ptr != 0) {
+        AtomAttributes attrs = AtomAttributes(pc.attr_ptr);
+        if (attrs.color != 0) {
+            FloatStream color = FloatStream(attrs.color);
+            uint c = gl_VertexIndex * 3;
+            vColor = vec3(color.data[c], color.data[c+1], color.data[c+2]);
+        }
+        if (attrs.roughness != 0) vRoughness = FloatStream(attrs.roughness).data[gl_VertexIndex];
+        if (attrs.metallic != 0) vMetallic = FloatStream(attrs.metallic).data[gl_VertexIndex];
     }
 }
 #endif
@@ -82,6 +162,7 @@ layout(location = 1) in vec3 vNormal; // Normale globale (forme)
 layout(location = 2) in vec3 vWorldPos;
 layout(location = 3) in float vMetallic;
 layout(location = 4) in float vRoughness;
+layout(location = 5) in vec3 vEmissive;
 
 layout(location = 0) out vec4 outColor;
 
@@ -149,7 +230,8 @@ void main() {
     vec3 radiance = vec3(3.0); 
 
     // PBR Paramètres
-    vec3 albedo = pow(vColor, vec3(2.2));
+    // "color" et base_color sont linéarisés au bake (texture.rs) : pas de décodage ici
+    vec3 albedo = vColor;
     float metallic = vMetallic;
     // Lissage des bords du voxel pour éviter le moiré
     float roughness = mix(vRoughness, 1.0, pow(mag, 4.0));
@@ -175,7 +257,7 @@ void main() {
     vec3 Lo = (kD * albedo / PI + specular) * radiance * NdotL;
     
     vec3 ambient = vec3(0.03) * albedo;
-    vec3 color = ambient + Lo;
+    vec3 color = ambient + Lo + vEmissive;
 
     // --- ACCUMULATION & TONEMAPPING ---
     ivec2 coords = ivec2(gl_FragCoord.xy);
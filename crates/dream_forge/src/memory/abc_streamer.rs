@@ -1,23 +1,385 @@
 // crates/dream_forge/src/memory/abc_streamer.rs
+//
+// Lecture d'animations de points (caches .seedanim) :
+//   - PlaybackClock  : temps de lecture (play / pause / scrub / boucle / vitesse)
+//   - FramePrefetcher : décodage des frames à venir sur un thread de fond
+//   - AbcStreamer    : trois slots VRAM, les copies sont enregistrées dans le
+//                      command buffer de la frame
+//
+// Les shaders lisent une paire de slots (frame courante et suivante) qu'ils
+// interpolent par atome ; les uploads vont dans le troisième, puis une barrière
+// transfert -> vertex précède la nouvelle liaison. Une frame à moitié écrite
+// n'est jamais visible.
+//
+// Contrainte : une seule frame en vol. Seule la paire liée en dernier est
+// protégée ; c'est suffisant parce que `begin_frame` attend la fence de l'unique
+// command buffer avant d'enregistrer la suivante, donc les slots de la paire
+// précédente ne sont plus lus quand on les réécrit. Avec plusieurs frames en vol,
+// il faudrait garder la paire soumise par chacune (et plus de trois slots).
 
 use crate::memory::{StagingBelt, MegaBuffer};
+use ash::vk;
 use dream_core::types::GpuPtr;
+use log::warn;
+use std::collections::HashMap;
+use std::sync::mpsc::{self, Receiver, Sender, TryRecvError};
+use std::thread::JoinHandle;
 
+/// `atom_layout` des shaders pour un slot streamé : positions float[3] seules
+pub const ANIM_ATOM_LAYOUT: u32 = 2;
+/// `atom_layout` d'un slot streamé avec vitesses : float[6] position + vitesse (Hermite)
+pub const ANIM_VELOCITY_LAYOUT: u32 = 3;
+
+/// Temps de lecture d'une animation de `frame_count` frames à `fps`
+#[derive(Clone, Debug)]
+pub struct PlaybackClock {
+    pub frame_count: u32,
+    pub fps: f32,
+    pub playing: bool,
+    pub looping: bool,
+    /// Multiplicateur de vitesse (1.0 = temps réel)
+    pub speed: f32,
+    /// Secondes depuis la frame 0
+    time: f64,
+}
+
+impl PlaybackClock {
+    pub const MIN_SPEED: f32 = 0.125;
+    pub const MAX_SPEED: f32 = 8.0;
+
+    pub fn new(frame_count: u32, fps: f32) -> Self {
+        Self { frame_count: frame_count.max(1), fps, playing: true, looping: true, speed: 1.0, time: 0.0 }
+    }
+
+    fn duration(&self) -> f64 {
+        self.frame_count as f64 / self.fps as f64
+    }
+
+    /// Fait avancer le temps de `dt` secondes réelles, renvoie la frame courante.
+    /// Sans boucle, la lecture s'arrête sur la dernière frame.
+    pub fn advance(&mut self, dt: f64) -> u32 {
+        if self.playing {
+            self.time += dt * self.speed as f64;
+            let duration = self.duration();
+            if self.time >= duration {
+                if self.looping {
+                    self.time %= duration;
+                } else {
+                    self.time = (self.frame_count - 1) as f64 / self.fps as f64;
+                    self.playing = false;
+                }
+            }
+        }
+        self.frame()
+    }
+
+    pub fn frame(&self) -> u32 {
+        ((self.time * self.fps as f64) as u32).min(self.frame_count - 1)
+    }
+
+    /// Frame vers laquelle interpoler : la suivante, la courante sur la dernière
+    /// (pas de fondu de la dernière frame vers la première en boucle)
+    pub fn next_frame(&self) -> u32 {
+        (self.frame() + 1).min(self.frame_count - 1)
+    }
+
+    /// Position de lecture dans la frame courante (0..1)
+    pub fn frame_fraction(&self) -> f32 {
+        (self.time * self.fps as f64).fract() as f32
+    }
+
+    pub fn toggle(&mut self) {
+        if !self.playing && !self.looping && self.frame() == self.frame_count - 1 {
+            self.time = 0.0; // Relance depuis le début une lecture arrivée au bout
+        }
+        self.playing = !self.playing;
+    }
+
+    /// Saute de `frames` frames (négatif = en arrière), en bouclant si la lecture boucle
+    pub fn step(&mut self, frames: i32) {
+        let count = self.frame_count as i64;
+        let target = self.frame() as i64 + frames as i64;
+        let target = if self.looping { target.rem_euclid(count) } else { target.clamp(0, count - 1) };
+        self.seek(target as u32);
+    }
+
+    pub fn seek(&mut self, frame: u32) {
+        self.time = frame.min(self.frame_count - 1) as f64 / self.fps as f64;
+    }
+
+    /// Multiplie la vitesse (bornée à MIN_SPEED..=MAX_SPEED)
+    pub fn scale_speed(&mut self, factor: f32) {
+        self.speed = (self.speed * factor).clamp(Self::MIN_SPEED, Self::MAX_SPEED);
+    }
+
+    /// Les `count` prochaines frames à partir de la courante, dans l'ordre de lecture
+    pub fn upcoming(&self, count: u32) -> Vec<u32> {
+        let current = self.frame();
+        (0..count.min(self.frame_count))
+            .map(|i| current + i)
+            .map_while(|f| match f < self.frame_count {
+                true => Some(f),
+                false => self.looping.then(|| f % self.frame_count),
+            })
+            .collect()
+    }
+}
+
+/// Décodage des frames sur un thread de fond.
+/// Le thread décode, dans l'ordre, la dernière liste demandée ; une nouvelle
+/// demande (scrub, boucle) interrompt la précédente.
+pub struct FramePrefetcher {
+    requests: Option<Sender<Vec<u32>>>,
+    results: Receiver<(u32, Option<Vec<f32>>)>,
+    ready: HashMap<u32, Vec<f32>>,
+    requested: Vec<u32>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl FramePrefetcher {
+    /// `load` décode une frame (None si illisible : elle est sautée)
+    pub fn spawn(mut load: impl FnMut(u32) -> Option<Vec<f32>> + Send + 'static) -> Self {
+        let (request_tx, request_rx) = mpsc::channel::<Vec<u32>>();
+        let (result_tx, result_rx) = mpsc::channel();
+
+        let thread = std::thread::Builder::new()
+            .name("abc-prefetch".into())
+            .spawn(move || {
+                let mut queue: Vec<u32> = Vec::new();
+                loop {
+                    // En attente s'il n'y a rien à faire, sinon on prend la demande la plus récente
+                    if queue.is_empty() {
+                        match request_rx.recv() {
+                            Ok(frames) => queue = frames,
+                            Err(_) => return,
+                        }
+                    }
+                    loop {
+                        match request_rx.try_recv() {
+                            Ok(frames) => queue = frames,
+                            Err(TryRecvError::Empty) => break,
+                            Err(TryRecvError::Disconnected) => return,
+                        }
+                    }
+                    if queue.is_empty() {
+                        continue;
+                    }
+                    let frame = queue.remove(0);
+                    if result_tx.send((frame, load(frame))).is_err() {
+                        return;
+                    }
+                }
+            })
+            .expect("❌ Impossible de lancer le thread de prefetch");
+
+        Self { requests: Some(request_tx), results: result_rx, ready: HashMap::new(), requested: Vec::new(), thread: Some(thread) }
+    }
+
+    fn poll(&mut self) {
+        while let Ok((frame, data)) = self.results.try_recv() {
+            self.receive(frame, data);
+        }
+    }
+
+    fn receive(&mut self, frame: u32, data: Option<Vec<f32>>) {
+        match data {
+            Some(data) => {
+                self.ready.insert(frame, data);
+            }
+            None => {
+                warn!("⚠️ [STREAM] Frame {} illisible, ignorée", frame);
+                self.requested.retain(|&f| f != frame);
+            }
+        }
+    }
+
+    /// Frames voulues, la plus urgente en tête. Les frames prêtes hors de la
+    /// liste sont libérées ; seules les manquantes partent au décodage.
+    pub fn request(&mut self, frames: &[u32]) {
+        self.poll();
+        self.ready.retain(|frame, _| frames.contains(frame));
+        let missing: Vec<u32> = frames.iter().copied().filter(|f| !self.ready.contains_key(f)).collect();
+        if missing != self.requested {
+            if let Some(requests) = &self.requests {
+                let _ = requests.send(missing.clone());
+            }
+            self.requested = missing;
+        }
+    }
+
+    /// Données de `frame` si elles sont décodées
+    pub fn take(&mut self, frame: u32) -> Option<Vec<f32>> {
+        self.poll();
+        let data = self.ready.remove(&frame);
+        if data.is_some() {
+            self.requested.retain(|&f| f != frame);
+        }
+        data
+    }
+
+    /// Attend le décodage de `frame` (chargement initial), None s'il échoue
+    pub fn wait(&mut self, frame: u32) -> Option<Vec<f32>> {
+        self.request(&[frame]);
+        loop {
+            if let Some(data) = self.take(frame) {
+                return Some(data);
+            }
+            let (decoded, data) = self.results.recv().ok()?;
+            let failed = decoded == frame && data.is_none();
+            self.receive(decoded, data);
+            if failed {
+                return None;
+            }
+        }
+    }
+}
+
+impl Drop for FramePrefetcher {
+    fn drop(&mut self) {
+        // Fermer le canal débloque le thread, qui se termine
+        self.requests = None;
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+/// Slots VRAM : la paire lue par les shaders (frame, suivante) + un slot d'upload
+const SLOT_COUNT: usize = 3;
+
+#[derive(Clone, Copy)]
+struct Slot {
+    offset: u64,
+    ptr: GpuPtr<f32>,
+    frame: Option<u32>,
+}
+
+/// Frames d'animation en VRAM (MegaBuffer). Les shaders lisent la paire liée
+/// (`vram_ptr`, `next_ptr`) et l'interpolent par `blend`.
 pub struct AbcStreamer {
+    /// Frame courante, lue par les shaders
     pub vram_ptr: GpuPtr<f32>,
-    pub buffer_offset: u64,
+    /// Frame suivante (= `vram_ptr` sans interpolation)
+    pub next_ptr: GpuPtr<f32>,
+    /// Position entre les deux frames (0..1)
+    pub blend: f32,
+    /// Frame de `vram_ptr` (None avant le premier `show`)
+    pub frame: Option<u32>,
+    next: Option<u32>,
+    slots: [Slot; SLOT_COUNT],
+    frame_size: u64,
 }
 
 impl AbcStreamer {
-    pub fn update_gpu(
-        &mut self, 
-        staging: &mut StagingBelt, 
-        new_data: &[f32]
+    /// Réserve les slots de `floats_per_frame` f32
+    pub fn new(universe: &mut MegaBuffer, floats_per_frame: u64) -> Self {
+        let frame_size = floats_per_frame * std::mem::size_of::<f32>() as u64;
+        let slots = [(); SLOT_COUNT].map(|_| {
+            let (offset, ptr) = universe.allocate::<f32>(frame_size, 16);
+            Slot { offset, ptr, frame: None }
+        });
+        Self { vram_ptr: slots[0].ptr, next_ptr: slots[0].ptr, blend: 0.0, frame: None, next: None, slots, frame_size }
+    }
+
+    /// Staging consommé au pire par un `show` : deux frames dans le même command
+    /// buffer, plus l'alignement de `StagingBelt::push` (256 octets) pour chacune
+    pub fn staging_size(&self) -> u64 {
+        2 * (self.frame_size + 256)
+    }
+
+    pub fn is_resident(&self, frame: u32) -> bool {
+        self.slots.iter().any(|s| s.frame == Some(frame))
+    }
+
+    fn slot_ptr(&self, frame: u32) -> Option<GpuPtr<f32>> {
+        self.slots.iter().find(|s| s.frame == Some(frame)).map(|s| s.ptr)
+    }
+
+    /// Slot réécrivable : ni la paire liée, ni `keep`. Un slot vide d'abord.
+    /// Suppose une seule frame en vol (voir l'en-tête du module).
+    fn free_slot(&self, keep: Option<u32>) -> Option<usize> {
+        let protected = [self.frame, self.next, keep];
+        let candidates = || (0..SLOT_COUNT).filter(|&i| self.slots[i].frame.is_none_or(|f| !protected.contains(&Some(f))));
+        candidates().find(|&i| self.slots[i].frame.is_none()).or_else(|| candidates().next())
+    }
+
+    /// Rend `frame` et `next` résidentes (données tirées de `fetch` pour celles qui
+    /// manquent), puis les lie pour les prochains draws. Si `frame` n'est pas
+    /// disponible, la paire précédente reste affichée. Les copies et leur barrière
+    /// sont enregistrées dans `cmd`, hors d'un rendering et avant les draws.
+    /// `staging` doit être vide et offrir au moins `staging_size()` octets.
+    /// Renvoie true si la géométrie affichée change.
+    #[allow(clippy::too_many_arguments)]
+    pub fn show(
+        &mut self,
+        device: &ash::Device,
+        cmd: vk::CommandBuffer,
+        staging: &mut StagingBelt,
+        universe: &MegaBuffer,
+        frame: u32,
+        next: u32,
+        blend: f32,
+        mut fetch: impl FnMut(u32) -> Option<Vec<f32>>,
+    ) -> bool {
+        for (wanted, keep) in [(frame, None), (next, Some(frame))] {
+            if self.is_resident(wanted) {
+                continue;
+            }
+            let Some(slot) = self.free_slot(keep) else { continue };
+            if let Some(data) = fetch(wanted) {
+                self.update_gpu(device, cmd, staging, universe, slot, wanted, &data);
+            }
+        }
+
+        let (frame, next, blend) = match (self.slot_ptr(frame), self.slot_ptr(next)) {
+            (Some(_), Some(_)) => (frame, next, blend),
+            (Some(_), None) => (frame, frame, 0.0),
+            (None, _) => return false,
+        };
+        let changed = self.frame != Some(frame) || self.next != Some(next) || self.blend != blend;
+        self.vram_ptr = self.slot_ptr(frame).unwrap();
+        self.next_ptr = self.slot_ptr(next).unwrap();
+        self.frame = Some(frame);
+        self.next = Some(next);
+        self.blend = blend;
+        changed
+    }
+
+    /// Enregistre dans `cmd` la copie de `new_data` (frame `frame`) vers `slot`,
+    /// suivie de la barrière transfert -> lecture shader
+    #[allow(clippy::too_many_arguments)]
+    fn update_gpu(
+        &mut self,
+        device: &ash::Device,
+        cmd: vk::CommandBuffer,
+        staging: &mut StagingBelt,
+        universe: &MegaBuffer,
+        slot: usize,
+        frame: u32,
+        new_data: &[f32],
     ) {
-        // On pousse les données de la frame actuelle sur le tapis roulant
-        staging.push(new_data);
-        
-        // Note : Au prochain Redraw, le CommandBuffer copiera 
-        // du Staging vers le vram_ptr dans le MegaBuffer.
+        let size = std::mem::size_of_val(new_data) as u64;
+        assert!(size <= self.frame_size, "❌ Frame {} plus grande que le slot ({} > {} octets)", frame, size, self.frame_size);
+        let (src_buffer, src_offset) = staging.push(new_data);
+
+        unsafe {
+            let copy = vk::BufferCopy { src_offset, dst_offset: self.slots[slot].offset, size };
+            device.cmd_copy_buffer(cmd, src_buffer, universe.buffer_handle(), &[copy]);
+            // Copie terminée avant toute lecture du slot (rendu et picker)
+            let barrier = vk::MemoryBarrier::builder()
+                .src_access_mask(vk::AccessFlags::TRANSFER_WRITE)
+                .dst_access_mask(vk::AccessFlags::SHADER_READ)
+                .build();
+            device.cmd_pipeline_barrier(
+                cmd,
+                vk::PipelineStageFlags::TRANSFER,
+                vk::PipelineStageFlags::VERTEX_SHADER | vk::PipelineStageFlags::COMPUTE_SHADER,
+                vk::DependencyFlags::empty(),
+                &[barrier],
+                &[],
+                &[],
+            );
+        }
+        self.slots[slot].frame = Some(frame);
     }
-}
\ No newline at end of file
+}
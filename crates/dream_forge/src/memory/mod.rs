@@ -1,9 +1,11 @@
 pub mod manager;
 pub mod staging;
 pub mod mega_buffer;
+pub mod abc_streamer;
 
 
 pub use manager::MemoryManager;
 pub use staging::StagingBelt;
 pub use mega_buffer::MegaBuffer;
+pub use abc_streamer::{AbcStreamer, FramePrefetcher, PlaybackClock};
 
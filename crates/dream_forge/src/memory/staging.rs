@@ -66,6 +66,11 @@ impl StagingBelt {
         (self.buffer, start_offset)
     }
 
+    /// Taille maximale d'un `push` (les gros uploads sont découpés à cette taille)
+    pub fn capacity(&self) -> u64 {
+        self.capacity
+    }
+
     /// Appelé à la fin de la frame pour remettre le pointeur à zéro
     /// (NOTE: Ne faire ça que si on est sûr que le GPU a fini de lire !)
     pub fn reset(&mut self) {
@@ -10,9 +10,49 @@ pub struct PipelineManager {
     pub compute_pipeline: vk::Pipeline,
     // 🆕 Nécessaire pour l'accumulation temporelle HDR
     pub descriptor_set_layout: vk::DescriptorSetLayout,
+    /// Rendu des gaussiennes 3DGS (absent tant que `create_splat_pipelines` n'a pas été appelé)
+    pub splat: Option<SplatPipelines>,
     device: ash::Device,
 }
 
+/// Chemin de rendu des gaussiennes : tri en profondeur (compute) puis quads mélangés
+pub struct SplatPipelines {
+    pub layout: vk::PipelineLayout,
+    pub pipeline: vk::Pipeline,
+    pub sort_layout: vk::PipelineLayout,
+    pub sort_pipeline: vk::Pipeline,
+}
+
+/// Taille des push constants du pipeline de surface (surface.glsl)
+pub const SURFACE_PUSH_SIZE: u32 = 192;
+
+/// Taille des push constants du pipeline de gaussiennes (splat.glsl)
+pub const SPLAT_PUSH_SIZE: u32 = 192;
+
+/// Threads par groupe de splat_sort.comp
+const SORT_GROUP_SIZE: u32 = 256;
+
+/// Paramètres du tri des gaussiennes (splat_sort.comp)
+pub struct SplatSort {
+    /// Chunk GEOM en VRAM
+    pub atoms_address: u64,
+    /// `float[padded_count(count)]` : profondeurs
+    pub keys_address: u64,
+    /// `uint[padded_count(count)]` : indices triés, lus par splat.glsl
+    pub order_address: u64,
+    pub count: u32,
+    pub atom_layout: u32,
+    /// 3e ligne de la matrice de vue : z vue = dot(depth_row, (pos, 1))
+    pub depth_row: [f32; 4],
+}
+
+impl SplatSort {
+    /// Le tri bitonique travaille sur une puissance de 2 (clés de bourrage à +inf)
+    pub fn padded_count(count: u32) -> u32 {
+        count.max(1).next_power_of_two()
+    }
+}
+
 impl PipelineManager {
     pub fn new(
         context: &ForgeContext,
@@ -40,12 +80,12 @@ impl PipelineManager {
                 .create_descriptor_set_layout(&descriptor_info, None)
                 .expect("❌ DescriptorSetLayout KO");
 
-            // --- 2. LAYOUT GRAPHIQUE (Alignement 160 octets) ---
-            // On passe à 160 octets pour aligner les mat4 sur des multiples de 16
+            // --- 2. LAYOUT GRAPHIQUE (192 octets) ---
+            // mat4 alignées sur des multiples de 16, frame animée suivante en fin de bloc
             let push_constant_range = vk::PushConstantRange::builder()
                 .stage_flags(vk::ShaderStageFlags::VERTEX | vk::ShaderStageFlags::FRAGMENT)
                 .offset(0)
-                .size(176); 
+                .size(SURFACE_PUSH_SIZE);
 
             let layouts = [descriptor_set_layout];
             let layout_info = vk::PipelineLayoutCreateInfo::builder()
@@ -58,7 +98,7 @@ impl PipelineManager {
             let compute_push_range = vk::PushConstantRange::builder()
                 .stage_flags(vk::ShaderStageFlags::COMPUTE)
                 .offset(0)
-                .size(64);
+                .size(80);
 
             let compute_layout_info = vk::PipelineLayoutCreateInfo::builder()
                 .push_constant_ranges(std::slice::from_ref(&compute_push_range));
@@ -79,61 +119,20 @@ impl PipelineManager {
                     .build(),
             ];
 
-            let vertex_input = vk::PipelineVertexInputStateCreateInfo::default();
-            let input_assembly = vk::PipelineInputAssemblyStateCreateInfo::builder()
-                .topology(vk::PrimitiveTopology::POINT_LIST)
-                .primitive_restart_enable(false);
-
-            let rasterizer = vk::PipelineRasterizationStateCreateInfo::builder()
-                .line_width(1.0)
-                .cull_mode(vk::CullModeFlags::NONE)
-                .polygon_mode(vk::PolygonMode::FILL);
-
-            let multisampling = vk::PipelineMultisampleStateCreateInfo::builder()
-                .rasterization_samples(vk::SampleCountFlags::TYPE_1);
-
-            let depth_stencil = vk::PipelineDepthStencilStateCreateInfo::builder()
-                .depth_test_enable(true)
-                .depth_write_enable(true)
-                .depth_compare_op(vk::CompareOp::LESS_OR_EQUAL);
-
             let color_blend_attachment = vk::PipelineColorBlendAttachmentState::builder()
                 .color_write_mask(vk::ColorComponentFlags::RGBA)
                 .blend_enable(false)
                 .build();
 
-            let color_blending = vk::PipelineColorBlendStateCreateInfo::builder()
-                .attachments(std::slice::from_ref(&color_blend_attachment));
-
-            let mut rendering_info = vk::PipelineRenderingCreateInfo::builder()
-                .color_attachment_formats(std::slice::from_ref(&color_format))
-                .depth_attachment_format(depth_format);
-
-            let dynamic_info = vk::PipelineDynamicStateCreateInfo::builder()
-                .dynamic_states(&[vk::DynamicState::VIEWPORT, vk::DynamicState::SCISSOR]);
-
-            let viewport_state = vk::PipelineViewportStateCreateInfo::builder()
-                .viewport_count(1)
-                .scissor_count(1);
-
-            let pipeline_info = vk::GraphicsPipelineCreateInfo::builder()
-                .push_next(&mut rendering_info)
-                .stages(&shader_stages)
-                .vertex_input_state(&vertex_input)
-                .input_assembly_state(&input_assembly)
-                .rasterization_state(&rasterizer)
-                .multisample_state(&multisampling)
-                .depth_stencil_state(&depth_stencil)
-                .color_blend_state(&color_blending)
-                .viewport_state(&viewport_state)
-                .dynamic_state(&dynamic_info)
-                .layout(layout);
-
-            let graphics_pipeline = context.device.create_graphics_pipelines(
-                vk::PipelineCache::null(),
-                std::slice::from_ref(&pipeline_info.build()),
-                None,
-            ).expect("❌ Échec Pipeline Graphique")[0];
+            let graphics_pipeline = create_graphics_pipeline(
+                &context.device,
+                layout,
+                &shader_stages,
+                vk::PrimitiveTopology::POINT_LIST,
+                color_blend_attachment,
+                true,
+                (color_format, depth_format),
+            );
 
             // --- 5. PIPELINE COMPUTE ---
             let compute_stage = vk::PipelineShaderStageCreateInfo::builder()
@@ -160,16 +159,240 @@ impl PipelineManager {
                 compute_layout,
                 compute_pipeline,
                 descriptor_set_layout,
+                splat: None,
                 device: context.device.clone(),
             }
         }
     }
+
+    /// Pipelines des gaussiennes 3DGS : quads générés par le vertex shader
+    /// (6 sommets par instance), mélange alpha du plus lointain au plus proche,
+    /// test de profondeur sans écriture ; tri bitonique en compute.
+    pub fn create_splat_pipelines(
+        &mut self,
+        context: &ForgeContext,
+        vert_shader: vk::ShaderModule,
+        frag_shader: vk::ShaderModule,
+        sort_shader: vk::ShaderModule,
+        color_format: vk::Format,
+        depth_format: vk::Format,
+    ) {
+        unsafe {
+            let entry_name = CString::new("main").unwrap();
+
+            let push_constant_range = vk::PushConstantRange::builder()
+                .stage_flags(vk::ShaderStageFlags::VERTEX | vk::ShaderStageFlags::FRAGMENT)
+                .offset(0)
+                .size(SPLAT_PUSH_SIZE);
+            let layout = context.device.create_pipeline_layout(
+                &vk::PipelineLayoutCreateInfo::builder().push_constant_ranges(std::slice::from_ref(&push_constant_range)),
+                None,
+            ).unwrap();
+
+            let shader_stages = [
+                vk::PipelineShaderStageCreateInfo::builder()
+                    .stage(vk::ShaderStageFlags::VERTEX)
+                    .module(vert_shader)
+                    .name(&entry_name)
+                    .build(),
+                vk::PipelineShaderStageCreateInfo::builder()
+                    .stage(vk::ShaderStageFlags::FRAGMENT)
+                    .module(frag_shader)
+                    .name(&entry_name)
+                    .build(),
+            ];
+
+            // "Over" classique : couleur non prémultipliée, alpha accumulé pour la composition
+            let color_blend_attachment = vk::PipelineColorBlendAttachmentState::builder()
+                .color_write_mask(vk::ColorComponentFlags::RGBA)
+                .blend_enable(true)
+                .src_color_blend_factor(vk::BlendFactor::SRC_ALPHA)
+                .dst_color_blend_factor(vk::BlendFactor::ONE_MINUS_SRC_ALPHA)
+                .color_blend_op(vk::BlendOp::ADD)
+                .src_alpha_blend_factor(vk::BlendFactor::ONE)
+                .dst_alpha_blend_factor(vk::BlendFactor::ONE_MINUS_SRC_ALPHA)
+                .alpha_blend_op(vk::BlendOp::ADD)
+                .build();
+
+            let pipeline = create_graphics_pipeline(
+                &context.device,
+                layout,
+                &shader_stages,
+                vk::PrimitiveTopology::TRIANGLE_LIST,
+                color_blend_attachment,
+                false,
+                (color_format, depth_format),
+            );
+
+            let sort_push_range = vk::PushConstantRange::builder()
+                .stage_flags(vk::ShaderStageFlags::COMPUTE)
+                .offset(0)
+                .size(64);
+            let sort_layout = context.device.create_pipeline_layout(
+                &vk::PipelineLayoutCreateInfo::builder().push_constant_ranges(std::slice::from_ref(&sort_push_range)),
+                None,
+            ).unwrap();
+
+            let sort_stage = vk::PipelineShaderStageCreateInfo::builder()
+                .stage(vk::ShaderStageFlags::COMPUTE)
+                .module(sort_shader)
+                .name(&entry_name)
+                .build();
+            let sort_info = vk::ComputePipelineCreateInfo::builder()
+                .stage(sort_stage)
+                .layout(sort_layout);
+            let sort_pipeline = context.device.create_compute_pipelines(
+                vk::PipelineCache::null(),
+                std::slice::from_ref(&sort_info.build()),
+                None,
+            ).expect("❌ Échec Pipeline Tri Splats")[0];
+
+            info!("✨ [PIPELINE] Gaussiennes : tri bitonique + mélange alpha prêts.");
+            if let Some(old) = self.splat.replace(SplatPipelines { layout, pipeline, sort_layout, sort_pipeline }) {
+                self.destroy_splat(old);
+            }
+        }
+    }
+
+    /// Enregistre le tri des gaussiennes du plus lointain au plus proche.
+    /// Hors d'un rendu dynamique ; une barrière rend l'ordre visible au vertex shader.
+    /// Une dispatch par étape bitonique : log2(n) * (log2(n) + 1) / 2 dispatches.
+    pub fn cmd_sort_splats(&self, cmd: vk::CommandBuffer, sort: &SplatSort) {
+        let Some(splat) = &self.splat else { return };
+        if sort.count == 0 {
+            return;
+        }
+        let padded = SplatSort::padded_count(sort.count);
+        let groups = padded.div_ceil(SORT_GROUP_SIZE);
+        let barrier = vk::MemoryBarrier::builder()
+            .src_access_mask(vk::AccessFlags::SHADER_WRITE)
+            .dst_access_mask(vk::AccessFlags::SHADER_READ | vk::AccessFlags::SHADER_WRITE)
+            .build();
+
+        unsafe {
+            self.device.cmd_bind_pipeline(cmd, vk::PipelineBindPoint::COMPUTE, splat.sort_pipeline);
+            let dispatch = |pass: u32, j: u32, k: u32| {
+                let mut pc = [0u8; 64];
+                pc[0..8].copy_from_slice(&sort.atoms_address.to_ne_bytes());
+                pc[8..16].copy_from_slice(&sort.keys_address.to_ne_bytes());
+                pc[16..24].copy_from_slice(&sort.order_address.to_ne_bytes());
+                pc[24..28].copy_from_slice(&sort.count.to_ne_bytes());
+                pc[28..32].copy_from_slice(&padded.to_ne_bytes());
+                pc[32..36].copy_from_slice(&pass.to_ne_bytes());
+                pc[36..40].copy_from_slice(&j.to_ne_bytes());
+                pc[40..44].copy_from_slice(&k.to_ne_bytes());
+                pc[44..48].copy_from_slice(&sort.atom_layout.to_ne_bytes());
+                for (c, value) in sort.depth_row.iter().enumerate() {
+                    pc[48 + c * 4..52 + c * 4].copy_from_slice(&value.to_ne_bytes());
+                }
+                self.device.cmd_push_constants(cmd, splat.sort_layout, vk::ShaderStageFlags::COMPUTE, 0, &pc);
+                self.device.cmd_dispatch(cmd, groups, 1, 1);
+                self.device.cmd_pipeline_barrier(cmd,
+                    vk::PipelineStageFlags::COMPUTE_SHADER, vk::PipelineStageFlags::COMPUTE_SHADER,
+                    vk::DependencyFlags::empty(), &[barrier], &[], &[]);
+            };
+
+            // Passe 0 : clés (z vue) et indices identité ; puis réseau bitonique
+            dispatch(0, 0, 0);
+            let mut k = 2;
+            while k <= padded {
+                let mut j = k / 2;
+                while j > 0 {
+                    dispatch(1, j, k);
+                    j /= 2;
+                }
+                k *= 2;
+            }
+
+            let to_vertex = vk::MemoryBarrier::builder()
+                .src_access_mask(vk::AccessFlags::SHADER_WRITE)
+                .dst_access_mask(vk::AccessFlags::SHADER_READ)
+                .build();
+            self.device.cmd_pipeline_barrier(cmd,
+                vk::PipelineStageFlags::COMPUTE_SHADER, vk::PipelineStageFlags::VERTEX_SHADER,
+                vk::DependencyFlags::empty(), &[to_vertex], &[], &[]);
+        }
+    }
+
+    fn destroy_splat(&self, splat: SplatPipelines) {
+        unsafe {
+            self.device.destroy_pipeline(splat.pipeline, None);
+            self.device.destroy_pipeline_layout(splat.layout, None);
+            self.device.destroy_pipeline(splat.sort_pipeline, None);
+            self.device.destroy_pipeline_layout(splat.sort_layout, None);
+        }
+    }
+}
+
+/// Pipeline graphique en rendu dynamique (viewport et scissor dynamiques)
+unsafe fn create_graphics_pipeline(
+    device: &ash::Device,
+    layout: vk::PipelineLayout,
+    shader_stages: &[vk::PipelineShaderStageCreateInfo],
+    topology: vk::PrimitiveTopology,
+    color_blend_attachment: vk::PipelineColorBlendAttachmentState,
+    depth_write: bool,
+    (color_format, depth_format): (vk::Format, vk::Format),
+) -> vk::Pipeline {
+    let vertex_input = vk::PipelineVertexInputStateCreateInfo::default();
+    let input_assembly = vk::PipelineInputAssemblyStateCreateInfo::builder()
+        .topology(topology)
+        .primitive_restart_enable(false);
+
+    let rasterizer = vk::PipelineRasterizationStateCreateInfo::builder()
+        .line_width(1.0)
+        .cull_mode(vk::CullModeFlags::NONE)
+        .polygon_mode(vk::PolygonMode::FILL);
+
+    let multisampling = vk::PipelineMultisampleStateCreateInfo::builder()
+        .rasterization_samples(vk::SampleCountFlags::TYPE_1);
+
+    let depth_stencil = vk::PipelineDepthStencilStateCreateInfo::builder()
+        .depth_test_enable(true)
+        .depth_write_enable(depth_write)
+        .depth_compare_op(vk::CompareOp::LESS_OR_EQUAL);
+
+    let color_blending = vk::PipelineColorBlendStateCreateInfo::builder()
+        .attachments(std::slice::from_ref(&color_blend_attachment));
+
+    let mut rendering_info = vk::PipelineRenderingCreateInfo::builder()
+        .color_attachment_formats(std::slice::from_ref(&color_format))
+        .depth_attachment_format(depth_format);
+
+    let dynamic_info = vk::PipelineDynamicStateCreateInfo::builder()
+        .dynamic_states(&[vk::DynamicState::VIEWPORT, vk::DynamicState::SCISSOR]);
+
+    let viewport_state = vk::PipelineViewportStateCreateInfo::builder()
+        .viewport_count(1)
+        .scissor_count(1);
+
+    let pipeline_info = vk::GraphicsPipelineCreateInfo::builder()
+        .push_next(&mut rendering_info)
+        .stages(shader_stages)
+        .vertex_input_state(&vertex_input)
+        .input_assembly_state(&input_assembly)
+        .rasterization_state(&rasterizer)
+        .multisample_state(&multisampling)
+        .depth_stencil_state(&depth_stencil)
+        .color_blend_state(&color_blending)
+        .viewport_state(&viewport_state)
+        .dynamic_state(&dynamic_info)
+        .layout(layout);
+
+    device.create_graphics_pipelines(
+        vk::PipelineCache::null(),
+        std::slice::from_ref(&pipeline_info.build()),
+        None,
+    ).expect("❌ Échec Pipeline Graphique")[0]
 }
 
 impl Drop for PipelineManager {
     fn drop(&mut self) {
         unsafe {
             info!("🧹 [PIPELINE] Destruction des ressources GPU...");
+            if let Some(splat) = self.splat.take() {
+                self.destroy_splat(splat);
+            }
             self.device.destroy_descriptor_set_layout(self.descriptor_set_layout, None);
             self.device.destroy_pipeline(self.graphics_pipeline, None);
             self.device.destroy_pipeline_layout(self.layout, None);
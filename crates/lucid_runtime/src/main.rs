@@ -1,5 +1,5 @@
 use winit::{
-    event::{Event, WindowEvent, DeviceEvent, ElementState, MouseButton, MouseScrollDelta},
+    event::{Event, WindowEvent, DeviceEvent, ElementState, KeyboardInput, MouseButton, MouseScrollDelta, VirtualKeyCode},
     event_loop::{ControlFlow, EventLoop},
     window::WindowBuilder,
 };
@@ -7,19 +7,128 @@ use dream_forge::{
     context::ForgeContext,
     renderer::ForgeRenderer,
     swapchain::ForgeSwapchain,
-    pipeline::PipelineManager,
+    pipeline::{PipelineManager, SplatSort, SPLAT_PUSH_SIZE, SURFACE_PUSH_SIZE},
     shader_compiler::ShaderCompiler,
-    memory::{StagingBelt, MegaBuffer},
+    memory::{StagingBelt, MegaBuffer, AbcStreamer, FramePrefetcher, PlaybackClock},
+    memory::abc_streamer::{ANIM_ATOM_LAYOUT, ANIM_VELOCITY_LAYOUT},
 };
-use seed_architect::importer::{SeedImporter, MaterialData};
-use seed_architect::SeedFileHeader;
+use seed_architect::importer::{
+    BakeSettings, MaterialData, COLOR_ATTRIBUTE, MATERIAL_ATTRIBUTE, METALLIC_ATTRIBUTE, ROUGHNESS_ATTRIBUTE,
+};
+use seed_architect::splat::{
+    SPLAT_OPACITY_ATTRIBUTE, SPLAT_ROTATION_ATTRIBUTE, SPLAT_SCALE_ATTRIBUTE, SPLAT_SH_ATTRIBUTE,
+};
+use seed_architect::anim::AnimReader;
+use seed_architect::asset_db::{AssetDatabase, AssetSettings, SourceSnapshot, ASSET_DATABASE};
+use seed_architect::{ChunkKind, SeedObject, SeedReader, SeedWriter};
 
 use ash::vk;
 use glam::{Mat4, Vec3, Vec4};
 use shaderc::ShaderKind;
-use std::fs::File;
-use std::io::Read;
-use log::info;
+use log::{info, warn};
+use std::time::Instant;
+
+/// Relique chargée sans argument, et sa source
+const DEFAULT_SEED: &str = "assets/processed/relic.seed";
+const DEFAULT_SOURCE: &str = "assets/raw/a.obj";
+
+/// Frames décodées d'avance par le prefetcher (~1 s à 24 fps)
+const PREFETCH_FRAMES: u32 = 24;
+
+/// Rendu entre deux frames d'animation (interpolé par atome dans le vertex shader)
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum Interpolation {
+    /// Frame la plus proche, sans fondu
+    Step,
+    Linear,
+    /// Courbe d'Hermite guidée par les vitesses (si le .seedanim en porte)
+    Hermite,
+}
+
+/// Lecture d'un .seedanim : horloge, décodage de fond et slots VRAM
+struct Playback {
+    clock: PlaybackClock,
+    streamer: AbcStreamer,
+    prefetcher: FramePrefetcher,
+    last_tick: Instant,
+    interpolation: Interpolation,
+    /// Slots entrelacés position + vitesse (ANIM_VELOCITY_LAYOUT)
+    velocities: bool,
+}
+
+impl Playback {
+    /// Espace : lecture / pause, ← → : frame précédente / suivante, Début : retour à 0,
+    /// L : boucle, ↑ ↓ : vitesse × 2 / ÷ 2, I : interpolation suivante.
+    /// Renvoie false si la touche n'est pas pour nous.
+    fn handle_key(&mut self, key: VirtualKeyCode) -> bool {
+        if key == VirtualKeyCode::I {
+            self.interpolation = match self.interpolation {
+                Interpolation::Step => Interpolation::Linear,
+                Interpolation::Linear if self.velocities => Interpolation::Hermite,
+                _ => Interpolation::Step,
+            };
+            info!("🎞️ Interpolation : {:?}", self.interpolation);
+            return true;
+        }
+        let clock = &mut self.clock;
+        match key {
+            VirtualKeyCode::Space => clock.toggle(),
+            VirtualKeyCode::Left => clock.step(-1),
+            VirtualKeyCode::Right => clock.step(1),
+            VirtualKeyCode::Home => clock.seek(0),
+            VirtualKeyCode::L => clock.looping = !clock.looping,
+            VirtualKeyCode::Up => clock.scale_speed(2.0),
+            VirtualKeyCode::Down => clock.scale_speed(0.5),
+            _ => return false,
+        }
+        info!(
+            "🎞️ Frame {}/{} {} x{}{}",
+            clock.frame(), clock.frame_count,
+            if clock.playing { "▶" } else { "⏸" },
+            clock.speed,
+            if clock.looping { " (boucle)" } else { "" },
+        );
+        true
+    }
+
+    /// Paire de frames à afficher et fondu entre elles selon le mode
+    fn target(&self) -> (u32, u32, f32) {
+        match self.interpolation {
+            Interpolation::Step => (self.clock.frame(), self.clock.frame(), 0.0),
+            _ => (self.clock.frame(), self.clock.next_frame(), self.clock.frame_fraction()),
+        }
+    }
+
+    fn atom_layout(&self) -> u32 {
+        if self.velocities { ANIM_VELOCITY_LAYOUT } else { ANIM_ATOM_LAYOUT }
+    }
+
+    /// Écart de temps entre deux frames pour les tangentes d'Hermite (0 : lerp)
+    fn hermite_dt(&self) -> f32 {
+        match self.interpolation {
+            Interpolation::Hermite => 1.0 / self.clock.fps,
+            _ => 0.0,
+        }
+    }
+}
+
+/// Re-bake `output` depuis `source` si la base d'assets le dit périmé
+fn refresh_asset(source: &str, output: &str) {
+    let mut assets = AssetDatabase::open(ASSET_DATABASE).unwrap_or_else(|e| {
+        warn!("⚠️ Base d'assets {} : {} (reconstruite)", ASSET_DATABASE, e);
+        AssetDatabase::reset(ASSET_DATABASE)
+    });
+    let settings = AssetSettings::Seed(BakeSettings::default());
+    let Some(reason) = assets.staleness(source, output, &settings) else { return };
+
+    info!("🔁 {} périmé ({}) : re-bake depuis {}", output, reason, source);
+    let snapshot = SourceSnapshot::of(source);
+    settings.bake(source, output).unwrap_or_else(|e| panic!("❌ Bake de {} KO : {}", source, e));
+    assets.record(snapshot, output, &settings);
+    if let Err(e) = assets.save() {
+        warn!("⚠️ Base d'assets {} non écrite : {}", ASSET_DATABASE, e);
+    }
+}
 
 fn main() {
     env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("info")).init();
@@ -46,51 +155,151 @@ fn main() {
     let mut universe = Some(MegaBuffer::new(&forge.memory, 1024 * 1024 * 1024));
     let mut staging = Some(StagingBelt::new(&forge.memory, 256 * 1024 * 1024));
 
-    // 2. Ingestion .SEED
-    let seed_path = "assets/processed/relic.seed";
-    if !std::path::Path::new(seed_path).exists() {
-        SeedImporter::import_and_bake("assets/raw/a.obj", seed_path);
-    }
+    // 2. Ingestion .SEED (premier argument, sinon la relique par défaut)
+    let seed_arg = std::env::args().nth(1);
+    let seed_path = seed_arg.clone().unwrap_or_else(|| DEFAULT_SEED.to_string());
+    let seed_path = seed_path.as_str();
+
+    // Animation .seedanim : un .seed proxy porte les objets, les frames arrivent en flux
+    let anim = seed_path.ends_with(".seedanim").then(|| {
+        AnimReader::open(seed_path).unwrap_or_else(|e| panic!("❌ Fichier .seedanim KO : {}", e))
+    });
+    let seed = match &anim {
+        Some(anim) => anim_proxy(anim),
+        None => {
+            // Relique par défaut re-bakée si a.obj, son .mtl ou ses textures ont changé.
+            // Un .seed demandé n'a pas de source connue : il doit exister.
+            if seed_arg.is_none() {
+                refresh_asset(DEFAULT_SOURCE, seed_path);
+            } else if !std::path::Path::new(seed_path).is_file() {
+                panic!("❌ Fichier .SEED introuvable : {} (à baker avec seed_architect)", seed_path);
+            }
+            SeedReader::open(seed_path).unwrap_or_else(|e| panic!("❌ Fichier .SEED KO : {}", e))
+        }
+    };
+    let header = *seed.header();
+    let geo_entry = match anim {
+        Some(_) => None,
+        None => Some(*seed.geometry_chunk().expect("❌ Data KO")),
+    };
+    let (stored_size, raw_size) = seed.payload_sizes();
+    info!("📦 .SEED : {} Ko stockés -> {} Ko décodés", stored_size / 1024, raw_size / 1024);
 
-    let mut file = File::open(seed_path).expect("❌ Fichier .SEED KO");
-    let header_size = std::mem::size_of::<SeedFileHeader>() + 8; 
-    let mut header_buf = vec![0u8; header_size];
-    file.read_exact(&mut header_buf).ok();
-    let header: SeedFileHeader = bincode::deserialize(&header_buf).expect("❌ Header KO");
+    let bvh_entry = seed.chunk(ChunkKind::Bvh).copied().filter(|e| e.count > 0);
 
-    let metadata = file.metadata().unwrap();
-    let remaining = metadata.len() - header_size as u64;
-    let mut vertex_data = vec![0u8; remaining as usize];
-    file.read_exact(&mut vertex_data).expect("❌ Data KO");
+    let geo_alloc = geo_entry.map(|e| universe.as_mut().unwrap().allocate::<u8>(e.raw_size, 16));
+    let geo_address = geo_alloc.map_or(0, |(_, ptr)| ptr.device_address);
+    // BVH optionnel : sans lui, le picker repasse en force brute (bvh_ptr = 0)
+    let bvh_alloc = bvh_entry.map(|e| universe.as_mut().unwrap().allocate::<u8>(e.raw_size, 16));
+    let bvh_address = bvh_alloc.map_or(0, |(_, ptr)| ptr.device_address);
 
-    let (geo_offset, geo_ptr) = universe.as_mut().unwrap().allocate::<u8>(vertex_data.len() as u64, 16);
-    let (_mat_offset, mat_ptr) = universe.as_mut().unwrap().allocate::<MaterialData>(64, 16);
+    // Matériaux du bake (Gold si l'OBJ n'en avait pas) + index par atome si multi-matériaux
+    let mut materials = seed.materials()
+        .unwrap_or_else(|e| panic!("❌ Matériaux KO : {}", e))
+        .into_owned();
+    if materials.is_empty() {
+        materials.push(MaterialData::GOLD);
+    }
+    info!("🎨 {} matériau(x)", materials.len());
+    let (mat_offset, mat_ptr) = universe.as_mut().unwrap().allocate::<MaterialData>(std::mem::size_of_val(&materials[..]) as u64, 16);
+
+    // Flux ATTR lus par surface.glsl, dans l'ordre de la table AtomAttributes
+    let mut attr_table = [0u64; 4];
+    let mut attr_uploads = Vec::new();
+    for (slot, name) in [MATERIAL_ATTRIBUTE, COLOR_ATTRIBUTE, ROUGHNESS_ATTRIBUTE, METALLIC_ATTRIBUTE].into_iter().enumerate() {
+        let Some((_, data)) = seed.attribute(name).unwrap_or_else(|e| panic!("❌ Attribut {} KO : {}", name, e)) else { continue };
+        let (offset, ptr) = universe.as_mut().unwrap().allocate::<f32>(std::mem::size_of_val(&data[..]) as u64, 16);
+        attr_table[slot] = ptr.device_address;
+        attr_uploads.push((offset, data.into_owned()));
+    }
+    let has_attributes = !attr_uploads.is_empty();
+    let (attr_offset, attr_ptr) = universe.as_mut().unwrap().allocate::<u64>(std::mem::size_of_val(&attr_table) as u64, 16);
+    attr_uploads.push((attr_offset, bytemuck::cast_slice(&attr_table).to_vec()));
+    let attr_address = if has_attributes { attr_ptr.device_address } else { 0 };
     let (res_offset, res_ptr) = universe.as_mut().unwrap().allocate::<u32>(16, 16);
 
-    let default_mat = [MaterialData {
-        base_color: [1.0, 0.84, 0.0],
-        metallic: 1.0,
-        roughness: 0.1,
-        ior: 1.45,
-        emissive_ptr: 0,
-        _padding: [0; 2],
-    }];
+    // Gaussiennes 3DGS : flux splat_* (table lue par splat.glsl) + clés et ordre du tri
+    let splats = seed.is_splats();
+    let mut splat_table = [0u64; 4];
+    let mut sh_count = 0u32;
+    let mut splat_address = 0u64;
+    let mut sort_buffers = None;
+    if splats {
+        for (slot, name) in [SPLAT_SCALE_ATTRIBUTE, SPLAT_ROTATION_ATTRIBUTE, SPLAT_OPACITY_ATTRIBUTE, SPLAT_SH_ATTRIBUTE].into_iter().enumerate() {
+            let (attr, data) = seed.attribute(name)
+                .unwrap_or_else(|e| panic!("❌ Attribut {} KO : {}", name, e))
+                .unwrap_or_else(|| panic!("❌ Attribut {} absent d'un .seed de gaussiennes", name));
+            if name == SPLAT_SH_ATTRIBUTE {
+                sh_count = attr.components / 3;
+            }
+            let (offset, ptr) = universe.as_mut().unwrap().allocate::<f32>(std::mem::size_of_val(&data[..]) as u64, 16);
+            splat_table[slot] = ptr.device_address;
+            attr_uploads.push((offset, data.into_owned()));
+        }
+        let (table_offset, table_ptr) = universe.as_mut().unwrap().allocate::<u64>(std::mem::size_of_val(&splat_table) as u64, 16);
+        attr_uploads.push((table_offset, bytemuck::cast_slice(&splat_table).to_vec()));
+        splat_address = table_ptr.device_address;
+
+        let padded = SplatSort::padded_count(header.vertex_count as u32) as u64;
+        let (_, keys_ptr) = universe.as_mut().unwrap().allocate::<f32>(padded * 4, 16);
+        let (_, order_ptr) = universe.as_mut().unwrap().allocate::<u32>(padded * 4, 16);
+        sort_buffers = Some((keys_ptr.device_address, order_ptr.device_address));
+        info!("✨ {} gaussiennes, {} coefficients SH par canal", header.vertex_count, sh_count);
+    }
+
+    // Objets nommés : un draw par objet visible, le picker ignore les objets masqués
+    let objects: Vec<SeedObject> = seed.objects()
+        .unwrap_or_else(|e| panic!("❌ Objets KO : {}", e))
+        .into_owned();
+    let mut visible = vec![true; objects.len()];
+    for object in &objects {
+        info!("🧩 Objet `{}` : {} atomes", object.name(), object.atom_count);
+    }
+    let (obj_offset, obj_ptr) = universe.as_mut().unwrap().allocate::<[u32; 4]>((objects.len().max(1) * 16) as u64, 16);
+    let objects_address = if objects.is_empty() { 0 } else { obj_ptr.device_address };
 
     unsafe {
         let cmd = renderer.command_buffer;
+
+        // Géométrie (+ BVH) : copie des pages mappées (ou décompression zstd en flux)
+        // vers le Ring, par tranches de la taille du StagingBelt
+        let slice_size = staging.as_ref().unwrap().capacity() as usize;
+        let uploads = geo_entry.zip(geo_alloc.map(|(offset, _)| offset))
+            .into_iter()
+            .chain(bvh_entry.zip(bvh_alloc.map(|(offset, _)| offset)));
+        for (entry, dst_offset) in uploads {
+            seed.stream_chunk(&entry, slice_size, |offset, piece| {
+                let _ = forge.device.begin_command_buffer(cmd, &vk::CommandBufferBeginInfo::builder().flags(vk::CommandBufferUsageFlags::ONE_TIME_SUBMIT));
+                let (s_buf, s_off) = staging.as_mut().unwrap().push(piece);
+                let copy = vk::BufferCopy::builder().src_offset(s_off).dst_offset(dst_offset + offset).size(piece.len() as u64);
+                forge.device.cmd_copy_buffer(cmd, s_buf, universe.as_ref().unwrap().buffer_handle(), &[copy.build()]);
+                let _ = forge.device.end_command_buffer(cmd);
+                forge.device.queue_submit(forge.queue, &[vk::SubmitInfo::builder().command_buffers(&[cmd]).build()], vk::Fence::null()).unwrap();
+                forge.device.device_wait_idle().unwrap();
+                staging.as_mut().unwrap().reset();
+            }).unwrap_or_else(|e| panic!("❌ Upload chunk {:?} KO : {}", entry.kind(), e));
+        }
+
         let _ = forge.device.begin_command_buffer(cmd, &vk::CommandBufferBeginInfo::builder().flags(vk::CommandBufferUsageFlags::ONE_TIME_SUBMIT));
-        
-        // FIX: On push le contenu de la slice (Vertex Data)
-        let (s_buf, s_off) = staging.as_mut().unwrap().push(&vertex_data[..]);
-        let copy_geo = vk::BufferCopy::builder().src_offset(s_off).dst_offset(geo_offset).size(vertex_data.len() as u64);
-        forge.device.cmd_copy_buffer(cmd, s_buf, universe.as_ref().unwrap().buffer_handle(), &[copy_geo.build()]);
-
-        // FIX: On push le contenu de la slice (Material) + Taille 40
-        let (s_buf_m, s_off_m) = staging.as_mut().unwrap().push(&default_mat[..]);
-        let copy_mat = vk::BufferCopy::builder().src_offset(s_off_m).dst_offset(_mat_offset).size(std::mem::size_of_val(&default_mat) as u64);
+
+        // Table des matériaux (48 octets chacun)
+        let (s_buf_m, s_off_m) = staging.as_mut().unwrap().push(&materials[..]);
+        let copy_mat = vk::BufferCopy::builder().src_offset(s_off_m).dst_offset(mat_offset).size(std::mem::size_of_val(&materials[..]) as u64);
         forge.device.cmd_copy_buffer(cmd, s_buf_m, universe.as_ref().unwrap().buffer_handle(), &[copy_mat.build()]);
 
-        let reset_res = [0u32, f32::MAX.to_bits()];
+        // Attributs par atome + leur table d'adresses
+        for (dst_offset, data) in &attr_uploads {
+            let (s_buf_a, s_off_a) = staging.as_mut().unwrap().push(&data[..]);
+            let copy_attr = vk::BufferCopy::builder().src_offset(s_off_a).dst_offset(*dst_offset).size(std::mem::size_of_val(&data[..]) as u64);
+            forge.device.cmd_copy_buffer(cmd, s_buf_a, universe.as_ref().unwrap().buffer_handle(), &[copy_attr.build()]);
+        }
+
+        let ranges = object_ranges(&objects, &visible);
+        let (s_buf_o, s_off_o) = staging.as_mut().unwrap().push(&ranges[..]);
+        let copy_obj = vk::BufferCopy::builder().src_offset(s_off_o).dst_offset(obj_offset).size(std::mem::size_of_val(&ranges[..]) as u64);
+        forge.device.cmd_copy_buffer(cmd, s_buf_o, universe.as_ref().unwrap().buffer_handle(), &[copy_obj.build()]);
+
+        let reset_res = [u32::MAX, f32::MAX.to_bits()];
         let (s_buf_r, s_off_r) = staging.as_mut().unwrap().push(bytemuck::cast_slice::<u32, u8>(&reset_res));
         let copy_res = vk::BufferCopy::builder().src_offset(s_off_r).dst_offset(res_offset).size(8);
         forge.device.cmd_copy_buffer(cmd, s_buf_r, universe.as_ref().unwrap().buffer_handle(), &[copy_res.build()]);
@@ -101,8 +310,60 @@ fn main() {
         staging.as_mut().unwrap().reset();
     }
 
+    // Tout est en VRAM : on libère le mapping du .seed
+    drop(seed);
+
+    // Animation : slots de positions (+ vitesses), frame 0 chargée avant la première image
+    let mut playback = anim.map(|mut reader| {
+        let clock = PlaybackClock::new(reader.frame_count(), reader.fps());
+        let velocities = reader.has_velocities();
+        info!(
+            "🎞️ Animation : {} frames à {} fps, {} points{}",
+            reader.frame_count(), reader.fps(), reader.atom_count(),
+            if velocities { ", vitesses" } else { "" },
+        );
+        let floats_per_atom = if velocities { 6 } else { 3 };
+        let mut streamer = AbcStreamer::new(universe.as_mut().unwrap(), reader.atom_count() * floats_per_atom);
+        // `show` peut pousser deux frames complètes dans le staging belt avant de soumettre
+        let capacity = staging.as_ref().unwrap().capacity();
+        if streamer.staging_size() > capacity {
+            panic!(
+                "❌ Animation trop lourde : {} points{} = {} Mo de staging par frame, le staging belt n'en a que {} Mo",
+                reader.atom_count(),
+                if velocities { " avec vitesses" } else { "" },
+                streamer.staging_size() / 1024 / 1024,
+                capacity / 1024 / 1024,
+            );
+        }
+        let mut prefetcher = FramePrefetcher::spawn(move |frame| {
+            let frame = reader.frame(frame)
+                .map_err(|e| warn!("⚠️ Frame {} KO : {}", frame, e))
+                .ok()?;
+            // La taille des slots suit le flag du header (vitesses sur toutes les frames) :
+            // une frame isolée avec vitesses reste en layout 2, positions seules
+            if !velocities {
+                return Some(frame.positions);
+            }
+            // Layout 3 : [position, vitesse] entrelacés par atome
+            let v = frame.velocities.unwrap_or_else(|| vec![0.0; frame.positions.len()]);
+            Some(frame.positions.chunks_exact(3).zip(v.chunks_exact(3)).flat_map(|(p, v)| p.iter().chain(v)).copied().collect())
+        });
+        unsafe {
+            let cmd = renderer.command_buffer;
+            let _ = forge.device.begin_command_buffer(cmd, &vk::CommandBufferBeginInfo::builder().flags(vk::CommandBufferUsageFlags::ONE_TIME_SUBMIT));
+            let shown = streamer.show(&forge.device, cmd, staging.as_mut().unwrap(), universe.as_ref().unwrap(), 0, 0, 0.0, |f| prefetcher.wait(f));
+            assert!(shown, "❌ Frame 0 de l'animation illisible");
+            let _ = forge.device.end_command_buffer(cmd);
+            forge.device.queue_submit(forge.queue, &[vk::SubmitInfo::builder().command_buffers(&[cmd]).build()], vk::Fence::null()).unwrap();
+            forge.device.device_wait_idle().unwrap();
+            staging.as_mut().unwrap().reset();
+        }
+        let interpolation = if velocities { Interpolation::Hermite } else { Interpolation::Linear };
+        Playback { clock, streamer, prefetcher, last_tick: Instant::now(), interpolation, velocities }
+    });
+
     // 3. Pipeline
-    let pipeline = PipelineManager::new(
+    let mut pipeline = PipelineManager::new(
         &forge,
         shader_compiler.compile_file(&forge.device, std::path::Path::new("assets/shaders/surface.glsl"), ShaderKind::Vertex).unwrap(),
         shader_compiler.compile_file(&forge.device, std::path::Path::new("assets/shaders/surface.glsl"), ShaderKind::Fragment).unwrap(),
@@ -110,6 +371,16 @@ fn main() {
         swapchain.format,
         swapchain.depth_format,
     );
+    if splats {
+        pipeline.create_splat_pipelines(
+            &forge,
+            shader_compiler.compile_file(&forge.device, std::path::Path::new("assets/shaders/splat.glsl"), ShaderKind::Vertex).unwrap(),
+            shader_compiler.compile_file(&forge.device, std::path::Path::new("assets/shaders/splat.glsl"), ShaderKind::Fragment).unwrap(),
+            shader_compiler.compile_file(&forge.device, std::path::Path::new("assets/shaders/splat_sort.comp"), ShaderKind::Compute).unwrap(),
+            swapchain.format,
+            swapchain.depth_format,
+        );
+    }
 
     // 4. Descriptor Accumulation
     let descriptor_pool = unsafe {
@@ -162,7 +433,12 @@ fn main() {
                     let ray_origin = near.truncate() / near.w;
                     let ray_dir = (far.truncate() / far.w - ray_origin).normalize();
 
-                    let reset_data: [u32; 2] = [0u32, f32::MAX.to_bits()];
+                    let reset_data: [u32; 2] = [u32::MAX, f32::MAX.to_bits()];
+                    // Animation : picking sur la frame courante, non interpolée
+                    let (atoms_address, atom_layout) = match &playback {
+                        Some(playback) => (playback.streamer.vram_ptr.device_address, playback.atom_layout()),
+                        None => (geo_address, header.atom_layout),
+                    };
                     unsafe {
                         let cmd = renderer.command_buffer;
                         let _ = forge.device.begin_command_buffer(cmd, &vk::CommandBufferBeginInfo::builder().flags(vk::CommandBufferUsageFlags::ONE_TIME_SUBMIT));
@@ -170,16 +446,22 @@ fn main() {
                         let (s_buf, s_off) = staging.as_mut().unwrap().push(bytemuck::cast_slice::<u32, u8>(&reset_data));
                         forge.device.cmd_copy_buffer(cmd, s_buf, universe.as_ref().unwrap().buffer_handle(), &[vk::BufferCopy { src_offset: s_off, dst_offset: res_offset, size: 8 }]);
                         
-                        let mut pc_compute = [0u8; 64];
-                        pc_compute[0..8].copy_from_slice(&geo_ptr.device_address.to_ne_bytes());
+                        let mut pc_compute = [0u8; 80];
+                        pc_compute[0..8].copy_from_slice(&atoms_address.to_ne_bytes());
                         pc_compute[8..16].copy_from_slice(&res_ptr.device_address.to_ne_bytes());
                         pc_compute[16..20].copy_from_slice(&(header.vertex_count as u32).to_ne_bytes());
+                        pc_compute[20..24].copy_from_slice(&atom_layout.to_ne_bytes());
+                        pc_compute[24..32].copy_from_slice(&bvh_address.to_ne_bytes());
                         pc_compute[32..44].copy_from_slice(bytemuck::cast_slice(&ray_origin.to_array()));
                         pc_compute[48..60].copy_from_slice(bytemuck::cast_slice(&ray_dir.to_array()));
+                        pc_compute[64..72].copy_from_slice(&objects_address.to_ne_bytes());
+                        pc_compute[72..76].copy_from_slice(&(objects.len() as u32).to_ne_bytes());
                         
                         forge.device.cmd_bind_pipeline(cmd, vk::PipelineBindPoint::COMPUTE, pipeline.compute_pipeline);
                         forge.device.cmd_push_constants(cmd, pipeline.compute_layout, vk::ShaderStageFlags::COMPUTE, 0, &pc_compute);
-                        forge.device.cmd_dispatch(cmd, (header.vertex_count as u32 + 255) / 256, 1, 1);
+                        // Avec BVH, un seul thread parcourt l'arbre
+                        let groups = if bvh_address != 0 { 1 } else { (header.vertex_count as u32 + 255) / 256 };
+                        forge.device.cmd_dispatch(cmd, groups, 1, 1);
                         
                         let _ = forge.device.end_command_buffer(cmd);
                         forge.device.queue_submit(forge.queue, &[vk::SubmitInfo::builder().command_buffers(&[cmd]).build()], vk::Fence::null()).unwrap();
@@ -187,10 +469,48 @@ fn main() {
                         staging.as_mut().unwrap().reset();
                         
                         let id = universe.as_ref().unwrap().read_value::<u32>(res_offset);
-                        if id != 0 { println!("🎯 IMPACT ! Atome #{}", id); }
+                        if id != u32::MAX {
+                            match objects.iter().find(|o| o.contains(id as u64)) {
+                                Some(o) => println!("🎯 IMPACT ! Atome #{} de l'objet `{}` (#{} global)", id as u64 - o.first_atom, o.name(), id),
+                                None => println!("🎯 IMPACT ! Atome #{}", id),
+                            }
+                        }
                     }
                 }
             }
+            // 1..9 : masque / affiche l'objet correspondant, 0 : tout afficher
+            Event::WindowEvent { event: WindowEvent::KeyboardInput { input: KeyboardInput { state: ElementState::Pressed, virtual_keycode: Some(key), .. }, .. }, .. } => {
+                if playback.as_mut().is_some_and(|p| p.handle_key(key)) {
+                    return;
+                }
+                let digits = [
+                    VirtualKeyCode::Key1, VirtualKeyCode::Key2, VirtualKeyCode::Key3,
+                    VirtualKeyCode::Key4, VirtualKeyCode::Key5, VirtualKeyCode::Key6,
+                    VirtualKeyCode::Key7, VirtualKeyCode::Key8, VirtualKeyCode::Key9,
+                ];
+                if key == VirtualKeyCode::Key0 {
+                    visible.iter_mut().for_each(|v| *v = true);
+                    info!("👁️ Tous les objets visibles");
+                } else if let Some(i) = digits.iter().position(|&d| d == key).filter(|&i| i < objects.len()) {
+                    visible[i] = !visible[i];
+                    info!("👁️ Objet `{}` {}", objects[i].name(), if visible[i] { "visible" } else { "masqué" });
+                } else {
+                    return;
+                }
+                frame_index = 0;
+
+                let ranges = object_ranges(&objects, &visible);
+                unsafe {
+                    let cmd = renderer.command_buffer;
+                    let _ = forge.device.begin_command_buffer(cmd, &vk::CommandBufferBeginInfo::builder().flags(vk::CommandBufferUsageFlags::ONE_TIME_SUBMIT));
+                    let (s_buf, s_off) = staging.as_mut().unwrap().push(&ranges[..]);
+                    forge.device.cmd_copy_buffer(cmd, s_buf, universe.as_ref().unwrap().buffer_handle(), &[vk::BufferCopy { src_offset: s_off, dst_offset: obj_offset, size: std::mem::size_of_val(&ranges[..]) as u64 }]);
+                    let _ = forge.device.end_command_buffer(cmd);
+                    forge.device.queue_submit(forge.queue, &[vk::SubmitInfo::builder().command_buffers(&[cmd]).build()], vk::Fence::null()).unwrap();
+                    forge.device.device_wait_idle().unwrap();
+                    staging.as_mut().unwrap().reset();
+                }
+            }
             Event::WindowEvent { event: WindowEvent::MouseWheel { delta, .. }, .. } => {
                 if let MouseScrollDelta::LineDelta(_, y) = delta { 
                     distance = (distance - y * 0.5).clamp(1.0, 50.0);
@@ -204,12 +524,43 @@ fn main() {
                     None => return,
                 };
                 renderer.begin_frame(&forge);
+                // La frame précédente est terminée (fence) : le Ring peut être réécrit
+                staging.as_mut().unwrap().reset();
 
                 unsafe {
                     let cmd = renderer.command_buffer;
                     let _ = forge.device.reset_command_buffer(cmd, vk::CommandBufferResetFlags::empty());
                     let _ = forge.device.begin_command_buffer(cmd, &vk::CommandBufferBeginInfo::builder().flags(vk::CommandBufferUsageFlags::ONE_TIME_SUBMIT));
 
+                    // Animation : la paire (frame, suivante) de l'horloge est copiée dans les slots
+                    // libres si elle est décodée, sinon on garde la précédente (pas d'attente
+                    // sur le thread de fond). Le vertex shader interpole entre les deux.
+                    if let Some(playback) = playback.as_mut() {
+                        let now = Instant::now();
+                        playback.clock.advance((now - playback.last_tick).as_secs_f64());
+                        playback.last_tick = now;
+                        let (frame, next, blend) = playback.target();
+                        let streamer = &mut playback.streamer;
+                        let wanted: Vec<u32> = playback.clock.upcoming(PREFETCH_FRAMES).into_iter()
+                            .filter(|&f| !streamer.is_resident(f))
+                            .collect();
+                        playback.prefetcher.request(&wanted);
+                        let prefetcher = &mut playback.prefetcher;
+                        if streamer.show(&forge.device, cmd, staging.as_mut().unwrap(), universe.as_ref().unwrap(), frame, next, blend, |f| prefetcher.take(f)) {
+                            frame_index = 0; // Nouvelle géométrie : l'accumulation repart de zéro
+                        }
+                    }
+                    let (geo_address, next_address, anim_blend, anim_dt, atom_layout) = match &playback {
+                        Some(playback) => (
+                            playback.streamer.vram_ptr.device_address,
+                            playback.streamer.next_ptr.device_address,
+                            playback.streamer.blend,
+                            playback.hermite_dt(),
+                            playback.atom_layout(),
+                        ),
+                        None => (geo_address, 0, 0.0f32, 0.0f32, header.atom_layout),
+                    };
+
                     // --- FIX TRANSITION SWAPCHAIN ---
                     let swapchain_barrier = vk::ImageMemoryBarrier::builder()
                         .image(swapchain.images[img_idx as usize])
@@ -237,30 +588,75 @@ fn main() {
                         .load_op(vk::AttachmentLoadOp::CLEAR).store_op(vk::AttachmentStoreOp::STORE)
                         .clear_value(vk::ClearValue { depth_stencil: vk::ClearDepthStencilValue { depth: 1.0, stencil: 0 } }).build();
 
-                    forge.device.cmd_begin_rendering(cmd, &vk::RenderingInfo::builder().render_area(vk::Rect2D { extent: swapchain.extent, ..Default::default() }).layer_count(1).color_attachments(std::slice::from_ref(&color_att)).depth_attachment(&depth_att));
-
                     let eye = Vec3::new(distance * pitch.to_radians().cos() * yaw.to_radians().cos(), distance * pitch.to_radians().sin(), distance * pitch.to_radians().cos() * yaw.to_radians().sin());
                     let view = Mat4::look_at_rh(eye, Vec3::ZERO, Vec3::Y);
                     let proj = Mat4::perspective_rh(45.0f32.to_radians(), swapchain.extent.width as f32 / swapchain.extent.height as f32, 0.1, 1000.0);
                     let correction = Mat4::from_cols(Vec4::new(1.0, 0.0, 0.0, 0.0), Vec4::new(0.0, 1.0, 0.0, 0.0), Vec4::new(0.0, 0.0, 0.5, 0.0), Vec4::new(0.0, 0.0, 0.5, 1.0));
                     let view_proj = correction * proj * view;
 
-                    let mut push_data = [0u8; 176];
-                    push_data[0..8].copy_from_slice(&geo_ptr.device_address.to_ne_bytes());
-                    push_data[8..16].copy_from_slice(&mat_ptr.device_address.to_ne_bytes());
-                    push_data[16..20].copy_from_slice(&frame_index.to_ne_bytes());
-                    push_data[32..44].copy_from_slice(bytemuck::cast_slice(&eye.to_array()));
-                    push_data[48..112].copy_from_slice(bytemuck::cast_slice(&Mat4::IDENTITY.to_cols_array()));
-                    push_data[112..176].copy_from_slice(bytemuck::cast_slice(&view_proj.to_cols_array()));
-
-                    forge.device.cmd_bind_descriptor_sets(cmd, vk::PipelineBindPoint::GRAPHICS, pipeline.layout, 0, &[accum_set], &[]);
-                    forge.device.cmd_bind_pipeline(cmd, vk::PipelineBindPoint::GRAPHICS, pipeline.graphics_pipeline);
-                    forge.device.cmd_push_constants(cmd, pipeline.layout, vk::ShaderStageFlags::VERTEX | vk::ShaderStageFlags::FRAGMENT, 0, &push_data);
-                    
+                    // Gaussiennes : re-tri seulement quand la caméra a bougé (frame_index remis à 0)
+                    if let Some((keys_address, order_address)) = sort_buffers.filter(|_| frame_index == 0) {
+                        pipeline.cmd_sort_splats(cmd, &SplatSort {
+                            atoms_address: geo_address,
+                            keys_address,
+                            order_address,
+                            count: header.vertex_count as u32,
+                            atom_layout: header.atom_layout,
+                            depth_row: view.row(2).to_array(),
+                        });
+                    }
+
+                    forge.device.cmd_begin_rendering(cmd, &vk::RenderingInfo::builder().render_area(vk::Rect2D { extent: swapchain.extent, ..Default::default() }).layer_count(1).color_attachments(std::slice::from_ref(&color_att)).depth_attachment(&depth_att));
+
                     forge.device.cmd_set_viewport(cmd, 0, &[vk::Viewport { x: 0.0, y: swapchain.extent.height as f32, width: swapchain.extent.width as f32, height: -(swapchain.extent.height as f32), min_depth: 0.0, max_depth: 1.0 }]);
                     forge.device.cmd_set_scissor(cmd, 0, &[vk::Rect2D { offset: vk::Offset2D { x: 0, y: 0 }, extent: swapchain.extent }]);
-                    forge.device.cmd_draw(cmd, header.vertex_count as u32, 1, 0, 0);
+
+                    if let (Some(splat), Some((_, order_address))) = (&pipeline.splat, sort_buffers) {
+                        let viewport = [swapchain.extent.width as f32, swapchain.extent.height as f32];
+                        let focal = [proj.x_axis.x * viewport[0] * 0.5, proj.y_axis.y * viewport[1] * 0.5];
+                        let mut splat_push = [0u8; SPLAT_PUSH_SIZE as usize];
+                        splat_push[0..8].copy_from_slice(&geo_address.to_ne_bytes());
+                        splat_push[8..16].copy_from_slice(&splat_address.to_ne_bytes());
+                        splat_push[16..24].copy_from_slice(&order_address.to_ne_bytes());
+                        splat_push[24..28].copy_from_slice(&header.atom_layout.to_ne_bytes());
+                        splat_push[28..32].copy_from_slice(&sh_count.to_ne_bytes());
+                        splat_push[32..44].copy_from_slice(bytemuck::cast_slice(&eye.to_array()));
+                        splat_push[48..56].copy_from_slice(bytemuck::cast_slice(&focal));
+                        splat_push[56..64].copy_from_slice(bytemuck::cast_slice(&viewport));
+                        splat_push[64..128].copy_from_slice(bytemuck::cast_slice(&view.to_cols_array()));
+                        splat_push[128..192].copy_from_slice(bytemuck::cast_slice(&view_proj.to_cols_array()));
+
+                        forge.device.cmd_bind_pipeline(cmd, vk::PipelineBindPoint::GRAPHICS, splat.pipeline);
+                        forge.device.cmd_push_constants(cmd, splat.layout, vk::ShaderStageFlags::VERTEX | vk::ShaderStageFlags::FRAGMENT, 0, &splat_push);
+                        // Un quad (6 sommets) par gaussienne, instances dans l'ordre trié
+                        forge.device.cmd_draw(cmd, 6, header.vertex_count as u32, 0, 0);
+                    } else {
+                        let mut push_data = [0u8; SURFACE_PUSH_SIZE as usize];
+                        push_data[0..8].copy_from_slice(&geo_address.to_ne_bytes());
+                        push_data[8..16].copy_from_slice(&mat_ptr.device_address.to_ne_bytes());
+                        push_data[16..20].copy_from_slice(&frame_index.to_ne_bytes());
+                        push_data[20..24].copy_from_slice(&atom_layout.to_ne_bytes());
+                        push_data[24..32].copy_from_slice(&attr_address.to_ne_bytes());
+                        push_data[32..44].copy_from_slice(bytemuck::cast_slice(&eye.to_array()));
+                        push_data[48..112].copy_from_slice(bytemuck::cast_slice(&Mat4::IDENTITY.to_cols_array()));
+                        push_data[112..176].copy_from_slice(bytemuck::cast_slice(&view_proj.to_cols_array()));
+                        push_data[176..184].copy_from_slice(&next_address.to_ne_bytes());
+                        push_data[184..188].copy_from_slice(&anim_blend.to_ne_bytes());
+                        push_data[188..192].copy_from_slice(&anim_dt.to_ne_bytes());
+
+                        forge.device.cmd_bind_descriptor_sets(cmd, vk::PipelineBindPoint::GRAPHICS, pipeline.layout, 0, &[accum_set], &[]);
+                        forge.device.cmd_bind_pipeline(cmd, vk::PipelineBindPoint::GRAPHICS, pipeline.graphics_pipeline);
+                        forge.device.cmd_push_constants(cmd, pipeline.layout, vk::ShaderStageFlags::VERTEX | vk::ShaderStageFlags::FRAGMENT, 0, &push_data);
                     
+                        if objects.is_empty() {
+                            forge.device.cmd_draw(cmd, header.vertex_count as u32, 1, 0, 0);
+                        }
+                        // first_vertex décale gl_VertexIndex : les attributs restent indexés globalement
+                        for (object, _) in objects.iter().zip(&visible).filter(|(_, &shown)| shown) {
+                            forge.device.cmd_draw(cmd, object.atom_count as u32, 1, object.first_atom as u32, 0);
+                        }
+                    }
+
                     forge.device.cmd_end_rendering(cmd);
                     let _ = forge.device.end_command_buffer(cmd).unwrap();
                 }
@@ -271,6 +667,7 @@ fn main() {
             Event::WindowEvent { event: WindowEvent::CloseRequested, .. } => {
                 unsafe {
                     let _ = forge.device.device_wait_idle();
+                    playback = None; // Arrêt du thread de prefetch
                     forge.device.destroy_descriptor_pool(descriptor_pool, None);
                     if let Some(u) = universe.take() { u.destroy(&forge.memory); }
                     if let Some(s) = staging.take() { s.destroy(&forge.memory); }
@@ -280,4 +677,21 @@ fn main() {
             _ => (),
         }
     });
-}
\ No newline at end of file
+}
+
+/// .seed minimal d'une animation : objets seuls, la géométrie arrive par l'AbcStreamer
+fn anim_proxy(anim: &AnimReader) -> SeedReader {
+    let mut bytes = Vec::new();
+    SeedWriter::new(anim.atom_count())
+        .add_pod_chunk(ChunkKind::Objects, anim.objects())
+        .write(&mut bytes)
+        .expect("❌ Proxy .seed KO");
+    SeedReader::from_bytes(&bytes).unwrap_or_else(|e| panic!("❌ Proxy .seed KO : {}", e))
+}
+
+/// ObjectRange (picker.comp) : first, count, visible, padding
+fn object_ranges(objects: &[SeedObject], visible: &[bool]) -> Vec<[u32; 4]> {
+    objects.iter().zip(visible)
+        .map(|(o, &shown)| [o.first_atom as u32, o.atom_count as u32, shown as u32, 0])
+        .collect()
+}
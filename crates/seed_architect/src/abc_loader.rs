@@ -1,31 +1,740 @@
 // crates/seed_architect/src/abc_loader.rs
+//
+// Lecteur Alembic (.abc) en Rust pur, conteneur Ogawa (l'ancien backend HDF5
+// n'est pas supporté).
+//
+// Ogawa est un arbre de groupes et de blocs de données adressés par offset u64
+// (bit de poids fort = donnée, offset 0 = vide). Alembic y range :
+//   racine   : [version, version lib, objet "ABC", métadonnées archive,
+//               échantillonnages temporels, métadonnées indexées]
+//   objet    : [propriétés, objets enfants..., en-têtes des enfants]
+//   compound : [propriétés..., en-têtes des propriétés]
+//   scalaire : un bloc [clé 16 o + valeur] par échantillon stocké
+//   tableau  : deux blocs [clé 16 o + valeurs] [dimensions u64] par échantillon stocké
+//
+// On lit la hiérarchie, les schémas Xform / PolyMesh / Points, puis les
+// positions (et vitesses) de chaque frame, en espace monde.
 
+use glam::{DMat3, DMat4, DVec3};
+use log::warn;
+use memmap2::Mmap;
+use std::fmt;
+use std::fs::File;
+use std::io;
+use std::path::Path;
+
+/// Erreur de lecture d'une archive Alembic
+#[derive(Debug)]
+pub enum AbcError {
+    IoError(io::Error),
+    /// Pas de signature "Ogawa" (fichier HDF5 ou autre)
+    NotOgawa,
+    /// Archive jamais finalisée (écriture interrompue)
+    NotFinalized,
+    UnsupportedVersion(i32),
+    /// Offset ou taille hors du fichier, structure inattendue
+    Corrupt { offset: u64, what: &'static str },
+    FrameOutOfRange { index: u32, count: u32 },
+}
+
+impl fmt::Display for AbcError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AbcError::IoError(e) => write!(f, "IO Error: {}", e),
+            AbcError::NotOgawa => write!(f, "Not an Ogawa Alembic archive (HDF5 archives are not supported)"),
+            AbcError::NotFinalized => write!(f, "Ogawa archive was never finalized (interrupted write)"),
+            AbcError::UnsupportedVersion(v) => write!(f, "Unsupported Alembic archive version: {}", v),
+            AbcError::Corrupt { offset, what } => write!(f, "Corrupt archive at offset {}: {}", offset, what),
+            AbcError::FrameOutOfRange { index, count } => {
+                write!(f, "Frame {} out of range ({} frames)", index, count)
+            }
+        }
+    }
+}
+
+impl std::error::Error for AbcError {}
+
+impl From<io::Error> for AbcError {
+    fn from(err: io::Error) -> Self {
+        AbcError::IoError(err)
+    }
+}
+
+// --- OGAWA ---
+
+/// Bit de poids fort d'une référence d'enfant : bloc de données (sinon groupe)
+const DATA_FLAG: u64 = 1 << 63;
+
+/// Version maximale de la couche Alembic sur Ogawa (ALEMBIC_OGAWA_FILE_VERSION)
+const ALEMBIC_OGAWA_FILE_VERSION: i32 = 0;
+
+/// Clé MD5 en tête de chaque bloc d'échantillon non vide
+const SAMPLE_KEY_SIZE: usize = 16;
+
+struct Ogawa {
+    map: Mmap,
+}
+
+impl Ogawa {
+    /// Mappe le fichier et renvoie la position du groupe racine
+    fn open(path: &Path) -> Result<(Self, u64), AbcError> {
+        let file = File::open(path)?;
+        // SAFETY: l'archive n'est pas modifiée pendant la lecture
+        let map = unsafe { Mmap::map(&file)? };
+        // "Ogawa" + octet figé (0xff une fois l'archive fermée) + version 16 bits
+        if map.len() < 16 || &map[..5] != b"Ogawa" {
+            return Err(AbcError::NotOgawa);
+        }
+        if map[5] != 0xff {
+            return Err(AbcError::NotFinalized);
+        }
+        let ogawa = Self { map };
+        let root = ogawa.u64_at(8)?;
+        Ok((ogawa, root))
+    }
+
+    fn slice(&self, offset: u64, len: u64, what: &'static str) -> Result<&[u8], AbcError> {
+        let end = offset.checked_add(len).filter(|&end| end <= self.map.len() as u64);
+        match end {
+            Some(end) => Ok(&self.map[offset as usize..end as usize]),
+            None => Err(AbcError::Corrupt { offset, what }),
+        }
+    }
+
+    fn u64_at(&self, offset: u64) -> Result<u64, AbcError> {
+        let bytes = self.slice(offset, 8, "truncated offset")?;
+        Ok(u64::from_le_bytes(bytes.try_into().unwrap()))
+    }
+
+    /// Références des enfants d'un groupe (le groupe 0 est vide)
+    fn group(&self, child: u64) -> Result<Vec<u64>, AbcError> {
+        if child & DATA_FLAG != 0 {
+            return Err(AbcError::Corrupt { offset: child & !DATA_FLAG, what: "expected a group, found data" });
+        }
+        if child == 0 {
+            return Ok(Vec::new());
+        }
+        let count = self.u64_at(child)?;
+        let bytes = self.slice(child + 8, count.saturating_mul(8), "group children out of bounds")?;
+        Ok(bytes.chunks_exact(8).map(|c| u64::from_le_bytes(c.try_into().unwrap())).collect())
+    }
+
+    /// Octets d'un bloc de données (le bloc 0 est vide)
+    fn data(&self, child: u64) -> Result<&[u8], AbcError> {
+        if child & DATA_FLAG == 0 {
+            return Err(AbcError::Corrupt { offset: child, what: "expected data, found a group" });
+        }
+        let offset = child & !DATA_FLAG;
+        if offset == 0 {
+            return Ok(&[]);
+        }
+        let size = self.u64_at(offset)?;
+        self.slice(offset + 8, size, "data block out of bounds")
+    }
+}
+
+/// Lecture séquentielle des blocs d'en-têtes
+struct Cursor<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Cursor<'a> {
+    fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes, pos: 0 }
+    }
+
+    fn take(&mut self, n: usize) -> Result<&'a [u8], AbcError> {
+        let end = self.pos.checked_add(n).filter(|&end| end <= self.bytes.len())
+            .ok_or(AbcError::Corrupt { offset: self.pos as u64, what: "truncated header block" })?;
+        let bytes = &self.bytes[self.pos..end];
+        self.pos = end;
+        Ok(bytes)
+    }
+
+    fn u8(&mut self) -> Result<u8, AbcError> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u32(&mut self) -> Result<u32, AbcError> {
+        Ok(u32::from_le_bytes(self.take(4)?.try_into().unwrap()))
+    }
+
+    fn f64(&mut self) -> Result<f64, AbcError> {
+        Ok(f64::from_le_bytes(self.take(8)?.try_into().unwrap()))
+    }
+
+    /// Entier sur 1, 2 ou 4 octets selon l'indice de taille des en-têtes de propriétés
+    fn hinted(&mut self, hint: u32) -> Result<u32, AbcError> {
+        Ok(match hint {
+            0 => self.u8()? as u32,
+            1 => u16::from_le_bytes(self.take(2)?.try_into().unwrap()) as u32,
+            _ => self.u32()?,
+        })
+    }
+
+    fn string(&mut self, len: usize) -> Result<String, AbcError> {
+        Ok(String::from_utf8_lossy(self.take(len)?).into_owned())
+    }
+
+    fn done(&self) -> bool {
+        self.pos >= self.bytes.len()
+    }
+}
+
+/// Valeur d'une clé dans des métadonnées sérialisées ("cle=valeur;cle=valeur")
+fn metadata_value<'a>(metadata: &'a str, key: &str) -> Option<&'a str> {
+    metadata.split(';').find_map(|pair| pair.split_once('=').filter(|(k, _)| *k == key).map(|(_, v)| v))
+}
+
+// --- TEMPS ---
+
+/// Échantillonnage temporel Alembic (uniforme, cyclique ou acyclique)
+#[derive(Clone, Debug)]
+pub struct TimeSampling {
+    /// Durée d'un cycle (uniforme : 1 / fps)
+    pub time_per_cycle: f64,
+    /// Temps des échantillons d'un cycle (tous les temps si acyclique)
+    pub times: Vec<f64>,
+    /// Nombre maximal d'échantillons d'une propriété de l'archive sur cet échantillonnage
+    pub max_samples: u32,
+}
+
+impl TimeSampling {
+    /// Marqueur Alembic d'un échantillonnage acyclique
+    const ACYCLIC_TIME_PER_CYCLE: f64 = f64::MAX / 32.0;
+
+    pub fn is_acyclic(&self) -> bool {
+        self.time_per_cycle == Self::ACYCLIC_TIME_PER_CYCLE
+    }
+
+    /// Temps de l'échantillon `index`
+    pub fn sample_time(&self, index: usize) -> f64 {
+        let Some(&last) = self.times.last() else {
+            return index as f64 * self.time_per_cycle;
+        };
+        if self.is_acyclic() {
+            return self.times.get(index).copied().unwrap_or(last);
+        }
+        let n = self.times.len();
+        self.times[index % n] + (index / n) as f64 * self.time_per_cycle
+    }
+
+    /// Dernier échantillon (parmi `count`) dont le temps est <= `time`
+    pub fn floor_index(&self, time: f64, count: usize) -> usize {
+        // Tolérance : 24 fps accumulés en f64 ne tombent pas pile sur i / 24
+        let time = time + 1e-6;
+        let (mut lo, mut hi) = (0, count);
+        while lo < hi {
+            let mid = (lo + hi) / 2;
+            if self.sample_time(mid) <= time { lo = mid + 1 } else { hi = mid }
+        }
+        lo.saturating_sub(1)
+    }
+
+    /// Échantillons par seconde (None si acyclique)
+    pub fn rate(&self) -> Option<f64> {
+        (!self.is_acyclic() && self.time_per_cycle > 0.0 && !self.times.is_empty())
+            .then(|| self.times.len() as f64 / self.time_per_cycle)
+    }
+}
+
+fn read_time_samplings(bytes: &[u8]) -> Result<Vec<TimeSampling>, AbcError> {
+    let mut cursor = Cursor::new(bytes);
+    let mut samplings = Vec::new();
+    while !cursor.done() {
+        let max_samples = cursor.u32()?;
+        let time_per_cycle = cursor.f64()?;
+        let count = cursor.u32()? as usize;
+        let times = (0..count).map(|_| cursor.f64()).collect::<Result<_, _>>()?;
+        samplings.push(TimeSampling { time_per_cycle, times, max_samples });
+    }
+    Ok(samplings)
+}
+
+// --- PROPRIÉTÉS ---
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum PropertyKind {
+    Compound,
+    Scalar,
+    Array,
+}
+
+/// Types POD Alembic (AbcCoreAbstract::PlainOldDataType)
+mod pod {
+    pub const BOOL: u8 = 0;
+    pub const U8: u8 = 1;
+    pub const I8: u8 = 2;
+    pub const U16: u8 = 3;
+    pub const I16: u8 = 4;
+    pub const U32: u8 = 5;
+    pub const I32: u8 = 6;
+    pub const U64: u8 = 7;
+    pub const I64: u8 = 8;
+    pub const F16: u8 = 9;
+    pub const F32: u8 = 10;
+    pub const F64: u8 = 11;
+}
+
+#[derive(Clone, Debug)]
+struct Property {
+    name: String,
+    kind: PropertyKind,
+    pod: u8,
+    extent: u8,
+    sample_count: u32,
+    first_changed: u32,
+    last_changed: u32,
+    time_sampling: u32,
+    /// Groupe Ogawa des échantillons (ou des sous-propriétés d'un compound)
+    group: u64,
+}
+
+impl Property {
+    /// Échantillon stocké pour l'index demandé : seuls les changements sont écrits
+    fn stored_index(&self, index: u32) -> usize {
+        let index = index.min(self.last_changed);
+        if (self.first_changed == 0 && self.last_changed == 0) || index < self.first_changed {
+            0
+        } else {
+            (index - self.first_changed + 1) as usize
+        }
+    }
+}
+
+/// Propriétés d'un compound : en-têtes dans le dernier bloc, une sous-propriété par groupe
+fn read_properties(ogawa: &Ogawa, group: u64) -> Result<Vec<Property>, AbcError> {
+    let children = ogawa.group(group)?;
+    let Some((&headers, groups)) = children.split_last() else {
+        return Ok(Vec::new());
+    };
+    let mut cursor = Cursor::new(ogawa.data(headers)?);
+    let mut properties = Vec::new();
+    while !cursor.done() {
+        let info = cursor.u32()?;
+        let hint = (info & 0xc) >> 2;
+        let kind = match info & 0x3 {
+            0 => PropertyKind::Compound,
+            1 => PropertyKind::Scalar,
+            _ => PropertyKind::Array, // 3 = tableau "scalar-like"
+        };
+        let mut property = Property {
+            name: String::new(),
+            kind,
+            pod: ((info & 0xf0) >> 4) as u8,
+            extent: ((info & 0xff000) >> 12) as u8,
+            sample_count: 0,
+            first_changed: 0,
+            last_changed: 0,
+            time_sampling: 0,
+            group: 0,
+        };
+        if kind != PropertyKind::Compound {
+            property.sample_count = cursor.hinted(hint)?;
+            if info & 0x200 != 0 {
+                property.first_changed = cursor.hinted(hint)?;
+                property.last_changed = cursor.hinted(hint)?;
+            } else if info & 0x800 == 0 {
+                // Ni constant ni bornes explicites : tout change après le premier échantillon
+                property.first_changed = 1;
+                property.last_changed = property.sample_count.saturating_sub(1);
+            }
+            if info & 0x100 != 0 {
+                property.time_sampling = cursor.hinted(hint)?;
+            }
+        }
+        let name_len = cursor.hinted(hint)? as usize;
+        property.name = cursor.string(name_len)?;
+        if (info & 0xff00000) >> 20 == 0xff {
+            // Métadonnées en ligne (trop longues pour la table indexée)
+            let len = cursor.hinted(hint)? as usize;
+            cursor.take(len)?;
+        }
+        property.group = *groups.get(properties.len())
+            .ok_or(AbcError::Corrupt { offset: group, what: "property header without group" })?;
+        properties.push(property);
+    }
+    Ok(properties)
+}
+
+fn find<'a>(properties: &'a [Property], name: &str, kind: PropertyKind) -> Option<&'a Property> {
+    properties.iter().find(|p| p.name == name && p.kind == kind)
+}
+
+/// Demi-flottant IEEE 754 -> f32
+fn f16_to_f32(bits: u16) -> f32 {
+    let sign = if bits & 0x8000 != 0 { -1.0 } else { 1.0 };
+    let exponent = ((bits >> 10) & 0x1f) as i32;
+    let mantissa = (bits & 0x3ff) as f32;
+    sign * match exponent {
+        0 => mantissa * 2f32.powi(-24),
+        31 if mantissa == 0.0 => f32::INFINITY,
+        31 => f32::NAN,
+        e => (1.0 + mantissa / 1024.0) * 2f32.powi(e - 15),
+    }
+}
+
+/// Valeurs numériques d'un bloc d'échantillon (clé retirée)
+fn decode_values(bytes: &[u8], pod_type: u8) -> Result<Vec<f64>, AbcError> {
+    macro_rules! values {
+        ($t:ty) => {
+            bytes.chunks_exact(std::mem::size_of::<$t>())
+                .map(|c| <$t>::from_le_bytes(c.try_into().unwrap()) as f64)
+                .collect()
+        };
+    }
+    Ok(match pod_type {
+        pod::BOOL | pod::U8 => bytes.iter().map(|&b| b as f64).collect(),
+        pod::I8 => bytes.iter().map(|&b| b as i8 as f64).collect(),
+        pod::U16 => values!(u16),
+        pod::I16 => values!(i16),
+        pod::U32 => values!(u32),
+        pod::I32 => values!(i32),
+        pod::U64 => values!(u64),
+        pod::I64 => values!(i64),
+        pod::F16 => bytes.chunks_exact(2).map(|c| f16_to_f32(u16::from_le_bytes([c[0], c[1]])) as f64).collect(),
+        pod::F32 => values!(f32),
+        pod::F64 => values!(f64),
+        _ => return Err(AbcError::Corrupt { offset: 0, what: "non-numeric property" }),
+    })
+}
+
+// --- OBJETS ---
+
+/// Schéma d'un objet de la hiérarchie
+#[derive(Clone, Debug, PartialEq)]
+pub enum AbcSchema {
+    Xform,
+    PolyMesh,
+    Points,
+    /// Autre schéma (caméra, courbes, SubD...) ou objet sans schéma : ignoré
+    Other(String),
+}
+
+/// Propriétés utiles d'un objet, selon son schéma
+#[derive(Clone, Debug)]
+enum ObjectData {
+    None,
+    Xform { ops: Vec<u8>, vals: Option<Property>, inherits: bool },
+    Geometry { positions: Property, velocities: Option<Property> },
+}
+
+/// Objet de la hiérarchie (parcours en profondeur : un parent précède ses enfants)
+#[derive(Clone, Debug)]
+pub struct AbcObject {
+    pub name: String,
+    /// Chemin complet ("/root/mesh")
+    pub path: String,
+    pub parent: Option<usize>,
+    pub schema: AbcSchema,
+    data: ObjectData,
+}
+
+/// Positions (et vitesses) de tous les PolyMesh / Points à un instant, en espace monde
 pub struct AbcFrame {
+    /// xyz concaténés, objets dans l'ordre de la hiérarchie
     pub positions: Vec<f32>,
+    /// Mêmes indices que `positions`, en unités par seconde.
+    /// None si un objet n'a pas de `.velocities` à cet instant.
+    pub velocities: Option<Vec<f32>>,
+    /// (index dans `AbcStream::objects`, nombre de sommets) de chaque objet géométrique
+    pub object_counts: Vec<(usize, u32)>,
+    pub time: f64,
 }
 
 pub struct AbcStream {
     pub path: String,
+    /// Frames à `fps` entre `start_time` et `end_time` inclus
     pub frame_count: u32,
     pub fps: f32,
+    pub start_time: f64,
+    pub end_time: f64,
+    ogawa: Ogawa,
+    time_samplings: Vec<TimeSampling>,
+    objects: Vec<AbcObject>,
 }
 
 impl AbcStream {
-    pub fn open(path: &str) -> Self {
-        // En 2030, on utilise des bindings vers la lib Alembic C++ 
-        // ou un parser Rust pur pour extraire les données.
-        Self {
+    pub fn handles(path: &str) -> bool {
+        Path::new(path).extension()
+            .and_then(|e| e.to_str())
+            .is_some_and(|e| e.eq_ignore_ascii_case("abc"))
+    }
+
+    pub fn open(path: &str) -> Result<Self, AbcError> {
+        let (ogawa, root) = Ogawa::open(Path::new(path))?;
+        let root = ogawa.group(root)?;
+        let is_data = |i: usize| root.get(i).is_some_and(|&c| c & DATA_FLAG != 0);
+        if root.len() < 6 || ![0, 1, 3, 4, 5].into_iter().all(is_data) || is_data(2) {
+            return Err(AbcError::Corrupt { offset: 8, what: "unexpected Alembic root layout" });
+        }
+
+        let version = ogawa.data(root[0])?;
+        let version = version.try_into().map(i32::from_le_bytes).unwrap_or(-1);
+        if !(0..=ALEMBIC_OGAWA_FILE_VERSION).contains(&version) {
+            return Err(AbcError::UnsupportedVersion(version));
+        }
+        let time_samplings = read_time_samplings(ogawa.data(root[4])?)?;
+
+        // Métadonnées indexées : l'index 0 désigne des métadonnées vides
+        let mut indexed = vec![String::new()];
+        let mut cursor = Cursor::new(ogawa.data(root[5])?);
+        while !cursor.done() {
+            let len = cursor.u8()? as usize;
+            indexed.push(cursor.string(len)?);
+        }
+
+        let mut stream = Self {
             path: path.to_string(),
-            frame_count: 240, // Exemple : 10 secondes à 24fps
+            frame_count: 1,
             fps: 24.0,
+            start_time: 0.0,
+            end_time: 0.0,
+            ogawa,
+            time_samplings,
+            objects: Vec::new(),
+        };
+        stream.read_hierarchy(root[2], None, &indexed)?;
+        stream.fit_frames();
+        Ok(stream)
+    }
+
+    /// Parcours en profondeur des enfants d'un objet : un parent précède ses enfants
+    fn read_hierarchy(&mut self, group: u64, parent: Option<usize>, indexed: &[String]) -> Result<(), AbcError> {
+        let children = self.ogawa.group(group)?;
+        let Some((&headers, groups)) = children.split_last().filter(|(&h, _)| h & DATA_FLAG != 0) else {
+            return Ok(());
+        };
+        // En-têtes des enfants, puis 32 octets d'empreintes (données, enfants).
+        // Copiés : la récursion ajoute des objets pendant la lecture
+        let headers = self.ogawa.data(headers)?.to_vec();
+        let mut cursor = Cursor::new(&headers[..headers.len().saturating_sub(32)]);
+        let mut index = 0;
+        while !cursor.done() {
+            let name_len = cursor.u32()? as usize;
+            let name = cursor.string(name_len)?;
+            let metadata = match cursor.u8()? {
+                0xff => {
+                    let len = cursor.u32()? as usize;
+                    cursor.string(len)?
+                }
+                i => indexed.get(i as usize).cloned().unwrap_or_default(),
+            };
+            // Groupe 0 = propriétés de l'objet, puis un groupe par enfant
+            index += 1;
+            let child_group = *groups.get(index)
+                .ok_or(AbcError::Corrupt { offset: group, what: "object header without group" })?;
+            let path = format!("{}/{}", parent.map_or("", |p| self.objects[p].path.as_str()), name);
+            let object = self.read_object(name, path, parent, &metadata, child_group)?;
+            self.objects.push(object);
+            self.read_hierarchy(child_group, Some(self.objects.len() - 1), indexed)?;
+        }
+        Ok(())
+    }
+
+    fn read_object(&self, name: String, path: String, parent: Option<usize>, metadata: &str, group: u64) -> Result<AbcObject, AbcError> {
+        let schema_name = metadata_value(metadata, "schema").unwrap_or("");
+        let schema = if schema_name.starts_with("AbcGeom_Xform") {
+            AbcSchema::Xform
+        } else if schema_name.starts_with("AbcGeom_PolyMesh") {
+            AbcSchema::PolyMesh
+        } else if schema_name.starts_with("AbcGeom_Points") {
+            AbcSchema::Points
+        } else {
+            AbcSchema::Other(schema_name.to_string())
+        };
+
+        let compound = match schema {
+            AbcSchema::Xform => ".xform",
+            AbcSchema::PolyMesh | AbcSchema::Points => ".geom",
+            AbcSchema::Other(_) => return Ok(AbcObject { name, path, parent, schema, data: ObjectData::None }),
+        };
+        let top = self.ogawa.group(group)?.first().copied().unwrap_or(0);
+        let top = read_properties(&self.ogawa, top)?;
+        let properties = match find(&top, compound, PropertyKind::Compound) {
+            Some(c) => read_properties(&self.ogawa, c.group)?,
+            None => Vec::new(),
+        };
+
+        let data = match schema {
+            AbcSchema::Xform => {
+                // .ops / .vals : scalaires (extent = nombre d'ops / de canaux) ou tableaux
+                let property = |name: &str| properties.iter().find(|p| p.name == name && p.kind != PropertyKind::Compound);
+                let ops = match property(".ops") {
+                    Some(ops) => self.sample_values(ops, 0)?.into_iter().map(|v| v as u8).collect(),
+                    None => Vec::new(),
+                };
+                let inherits = match property(".inherits") {
+                    Some(inherits) => self.sample_values(inherits, 0)?.first().is_none_or(|&v| v != 0.0),
+                    None => true,
+                };
+                ObjectData::Xform { ops, vals: property(".vals").cloned(), inherits }
+            }
+            _ => match find(&properties, "P", PropertyKind::Array) {
+                Some(p) if p.extent == 3 => ObjectData::Geometry {
+                    positions: p.clone(),
+                    velocities: find(&properties, ".velocities", PropertyKind::Array).filter(|v| v.extent == 3).cloned(),
+                },
+                _ => {
+                    warn!("⚠️ Alembic : `{}` sans positions P (vec3) exploitables, ignoré", path);
+                    ObjectData::None
+                }
+            },
+        };
+        Ok(AbcObject { name, path, parent, schema, data })
+    }
+
+    /// Plage et cadence des frames : union des propriétés animées,
+    /// cadence de l'échantillonnage le plus dense
+    fn fit_frames(&mut self) {
+        let animated: Vec<(&TimeSampling, u32)> = self.objects.iter()
+            .flat_map(|o| match &o.data {
+                ObjectData::Xform { vals, .. } => vec![vals.as_ref()],
+                ObjectData::Geometry { positions, velocities } => vec![Some(positions), velocities.as_ref()],
+                ObjectData::None => Vec::new(),
+            })
+            .flatten()
+            .filter(|p| p.sample_count > 1)
+            .filter_map(|p| Some((self.time_samplings.get(p.time_sampling as usize)?, p.sample_count)))
+            .collect();
+        let Some(&(primary, count)) = animated.iter().max_by_key(|(_, count)| *count) else {
+            return; // Archive statique : une seule frame
+        };
+
+        self.start_time = animated.iter().map(|(ts, _)| ts.sample_time(0)).fold(f64::MAX, f64::min);
+        self.end_time = animated.iter().map(|(ts, n)| ts.sample_time(*n as usize - 1)).fold(f64::MIN, f64::max);
+        let span = primary.sample_time(count as usize - 1) - primary.sample_time(0);
+        let fps = primary.rate().unwrap_or((count - 1) as f64 / span.max(f64::EPSILON));
+        self.fps = fps as f32;
+        self.frame_count = ((self.end_time - self.start_time) * fps).round() as u32 + 1;
+    }
+
+    pub fn objects(&self) -> &[AbcObject] {
+        &self.objects
+    }
+
+    /// Échantillonnages de l'archive (l'index 0 est l'identité : 1 s par échantillon)
+    pub fn time_samplings(&self) -> &[TimeSampling] {
+        &self.time_samplings
+    }
+
+    /// Temps (secondes) de la frame `index`
+    pub fn frame_time(&self, index: u32) -> f64 {
+        self.start_time + index as f64 / self.fps as f64
+    }
+
+    pub fn load_frame(&self, frame_index: u32) -> Result<AbcFrame, AbcError> {
+        if frame_index >= self.frame_count {
+            return Err(AbcError::FrameOutOfRange { index: frame_index, count: self.frame_count });
+        }
+        self.load_time(self.frame_time(frame_index))
+    }
+
+    /// Géométrie à un instant quelconque (échantillon précédent le plus proche, sans interpolation)
+    pub fn load_time(&self, time: f64) -> Result<AbcFrame, AbcError> {
+        let world = self.world_matrices(time)?;
+        let mut frame = AbcFrame { positions: Vec::new(), velocities: Some(Vec::new()), object_counts: Vec::new(), time };
+        for (i, object) in self.objects.iter().enumerate() {
+            let ObjectData::Geometry { positions, velocities } = &object.data else { continue };
+            let index = self.sample_index(positions, time);
+            let points = self.sample_values(positions, index)?;
+            let start = frame.positions.len();
+            frame.positions.extend(points.chunks_exact(3).map(|p| world[i].transform_point3(DVec3::new(p[0], p[1], p[2])))
+                .flat_map(|p| [p.x as f32, p.y as f32, p.z as f32]));
+            let count = (frame.positions.len() - start) / 3;
+            frame.object_counts.push((i, count as u32));
+
+            // Vitesses : même échantillon que P, tournées / mises à l'échelle par le monde
+            let velocities = match velocities {
+                Some(v) => Some(self.sample_values(v, self.sample_index(v, time))?).filter(|v| v.len() == count * 3),
+                None => None,
+            };
+            match (velocities, frame.velocities.as_mut()) {
+                (Some(values), Some(out)) => {
+                    let linear = DMat3::from_mat4(world[i]);
+                    out.extend(values.chunks_exact(3).map(|v| linear * DVec3::new(v[0], v[1], v[2]))
+                        .flat_map(|v| [v.x as f32, v.y as f32, v.z as f32]));
+                }
+                _ => frame.velocities = None,
+            }
+        }
+        Ok(frame)
+    }
+
+    fn sample_index(&self, property: &Property, time: f64) -> u32 {
+        match self.time_samplings.get(property.time_sampling as usize) {
+            Some(ts) => ts.floor_index(time, property.sample_count.max(1) as usize) as u32,
+            None => 0,
+        }
+    }
+
+    /// Valeurs de l'échantillon `index` d'une propriété scalaire ou tableau
+    fn sample_values(&self, property: &Property, index: u32) -> Result<Vec<f64>, AbcError> {
+        let children = self.ogawa.group(property.group)?;
+        let stored = property.stored_index(index);
+        // Tableau : [données, dimensions] par échantillon
+        let slot = if property.kind == PropertyKind::Array { stored * 2 } else { stored };
+        let Some(&data) = children.get(slot) else {
+            return Err(AbcError::Corrupt { offset: property.group, what: "missing property sample" });
+        };
+        let bytes = self.ogawa.data(data)?;
+        if bytes.len() < SAMPLE_KEY_SIZE {
+            return Ok(Vec::new()); // Tableau vide
         }
+        decode_values(&bytes[SAMPLE_KEY_SIZE..], property.pod)
     }
 
-    pub fn load_frame(&self, _frame_index: u32) -> AbcFrame {
-        // Extraction réelle des données binaire du fichier .abc
-        // Pour l'instant, on simule le retour des positions d'atomes
-        AbcFrame {
-            positions: vec![0.0; 3000], // Simule 1000 atomes
+    /// Matrices monde de tous les objets (Xform : ops composées, autres : identité locale)
+    fn world_matrices(&self, time: f64) -> Result<Vec<DMat4>, AbcError> {
+        let mut world: Vec<DMat4> = Vec::with_capacity(self.objects.len());
+        for object in &self.objects {
+            let parent = object.parent.map_or(DMat4::IDENTITY, |p| world[p]);
+            let matrix = match &object.data {
+                ObjectData::Xform { ops, vals, inherits } => {
+                    let local = match vals {
+                        Some(vals) => xform_matrix(ops, &self.sample_values(vals, self.sample_index(vals, time))?),
+                        None => DMat4::IDENTITY,
+                    };
+                    if *inherits { parent * local } else { local }
+                }
+                _ => parent,
+            };
+            world.push(matrix);
         }
+        Ok(world)
     }
-}
\ No newline at end of file
+}
+
+/// Matrice locale d'un Xform : opérations dans l'ordre du fichier, la dernière
+/// appliquée en premier au point (M = op0 * op1 * ... en vecteurs colonnes).
+/// Code d'op = type << 4 | indice ; les rotations sont en degrés.
+fn xform_matrix(ops: &[u8], vals: &[f64]) -> DMat4 {
+    let mut matrix = DMat4::IDENTITY;
+    let mut channel = 0;
+    for &op in ops {
+        let c = &vals[channel.min(vals.len())..];
+        let vec3 = |c: &[f64]| DVec3::new(c[0], c[1], c[2]);
+        let (local, channels) = match op >> 4 {
+            0 if c.len() >= 3 => (DMat4::from_scale(vec3(c)), 3),
+            1 if c.len() >= 3 => (DMat4::from_translation(vec3(c)), 3),
+            2 if c.len() >= 4 => {
+                let axis = vec3(c).normalize_or_zero();
+                let rotation = if axis == DVec3::ZERO { DMat4::IDENTITY } else { DMat4::from_axis_angle(axis, c[3].to_radians()) };
+                (rotation, 4)
+            }
+            // Matrice Imath (lignes, vecteurs lignes) : lue en colonnes = transposée
+            3 if c.len() >= 16 => (DMat4::from_cols_array(c[..16].try_into().unwrap()), 16),
+            4 if !c.is_empty() => (DMat4::from_rotation_x(c[0].to_radians()), 1),
+            5 if !c.is_empty() => (DMat4::from_rotation_y(c[0].to_radians()), 1),
+            6 if !c.is_empty() => (DMat4::from_rotation_z(c[0].to_radians()), 1),
+            _ => {
+                warn!("⚠️ Alembic : opération Xform {:#x} invalide, ignorée avec la suite", op);
+                break;
+            }
+        };
+        matrix *= local;
+        channel += channels;
+    }
+    matrix
+}
@@ -0,0 +1,652 @@
+// crates/seed_architect/src/anim.rs
+//
+// Seeds animées (.seedanim) : caches de points / sommets baked depuis Alembic.
+//
+//   [AnimFileHeader (128 o)] [SeedObject × object_count] [FrameEntry × frame_count] [payloads]
+//
+// Chaque frame est un payload zstd indépendant :
+//   - keyframe : positions f32 brutes (la première frame en est toujours une)
+//   - delta    : écart à sa keyframe, quantifié en i16 avec un pas propre à la frame
+//   - + vitesses quantifiées en i16 si la source en fournit
+// Les deltas sont relatifs à la keyframe (pas à la frame précédente) : n'importe
+// quelle frame se décode avec au plus deux payloads, sans rejouer la séquence.
+// Une nouvelle keyframe est émise dès que le pas de quantification dépasserait
+// la tolérance du bake. Les octets sont rangés par plans avant zstd (poids
+// faibles puis poids forts) : les deltas lisses s'y compressent bien mieux.
+
+use bytemuck::{Pod, Zeroable};
+use memmap2::Mmap;
+use std::fmt;
+use std::fs::File;
+use std::io::{self, Write};
+use std::path::Path;
+
+use crate::abc_loader::{AbcError, AbcStream};
+use crate::format::{checksum, write_atomically, SeedObject};
+use log::info;
+
+/// Signature en tête de fichier
+pub const ANIM_MAGIC: [u8; 4] = *b"SANM";
+
+/// Version du conteneur .seedanim
+pub const ANIM_VERSION: u32 = 1;
+
+/// `AnimFileHeader::flags` : toutes les frames portent des vitesses
+pub const ANIM_FLAG_VELOCITIES: u32 = 1 << 0;
+
+/// `FrameEntry::flags` : positions f32 brutes (sinon delta i16 vers `keyframe`)
+pub const FRAME_FLAG_KEY: u32 = 1 << 0;
+/// `FrameEntry::flags` : vitesses i16 à la suite des positions
+pub const FRAME_FLAG_VELOCITIES: u32 = 1 << 1;
+
+/// Header fixe de 128 octets, lisible tel quel par `bytemuck`.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct AnimFileHeader {
+    pub magic: [u8; 4],      // "SANM"
+    pub version: u32,        // ANIM_VERSION
+    pub atom_count: u64,     // Points par frame (constant)
+    pub frame_count: u32,
+    pub fps: f32,
+    pub start_time: f64,     // Temps de la frame 0 (secondes)
+    pub flags: u32,          // ANIM_FLAG_*
+    pub object_count: u32,   // Entrées SeedObject après le header
+    pub tolerance: f32,      // Erreur maximale des deltas (unités monde)
+    pub _padding: u32,
+    pub min: [f32; 3],       // AABB de toutes les frames
+    pub max: [f32; 3],
+    pub content_hash: [u8; 32], // blake3(source + réglages) : détection des bakes périmés
+    pub _reserved: [u64; 3],
+}
+
+/// Entrée de la table des frames (40 octets)
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct FrameEntry {
+    pub offset: u64,         // Position absolue du payload zstd
+    pub size: u32,           // Octets stockés
+    pub raw_size: u32,       // Octets décodés
+    pub keyframe: u32,       // Frame de référence (elle-même pour une keyframe)
+    pub flags: u32,          // FRAME_FLAG_*
+    pub position_step: f32,  // Pas de quantification des deltas (0 pour une keyframe)
+    pub velocity_step: f32,  // Pas de quantification des vitesses
+    pub checksum: u32,       // CRC32 des octets stockés
+    pub _padding: u32,
+}
+
+impl FrameEntry {
+    pub fn is_key(&self) -> bool {
+        self.flags & FRAME_FLAG_KEY != 0
+    }
+
+    pub fn has_velocities(&self) -> bool {
+        self.flags & FRAME_FLAG_VELOCITIES != 0
+    }
+}
+
+/// Erreur de bake ou de lecture d'un .seedanim
+#[derive(Debug)]
+pub enum AnimError {
+    IoError(io::Error),
+    Alembic(AbcError),
+    BadMagic([u8; 4]),
+    UnsupportedVersion(u32),
+    /// Le fichier est plus court que ce qu'annoncent le header ou la table
+    Truncated { expected: u64, actual: u64 },
+    /// Le nombre de points change d'une frame à l'autre (topologie variable)
+    TopologyChanged { frame: u32, expected: u64, actual: u64 },
+    /// Payload de frame incohérent (taille décodée, checksum)
+    CorruptFrame(u32),
+    FrameOutOfRange { index: u32, count: u32 },
+}
+
+impl fmt::Display for AnimError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AnimError::IoError(e) => write!(f, "IO Error: {}", e),
+            AnimError::Alembic(e) => write!(f, "Alembic Error: {}", e),
+            AnimError::BadMagic(magic) => write!(f, "Wrong magic: {:?} (expected \"SANM\")", magic),
+            AnimError::UnsupportedVersion(v) => {
+                write!(f, "Unsupported version: {} (expected {})", v, ANIM_VERSION)
+            }
+            AnimError::Truncated { expected, actual } => {
+                write!(f, "Truncated file: expected at least {} bytes, got {}", expected, actual)
+            }
+            AnimError::TopologyChanged { frame, expected, actual } => write!(
+                f,
+                "Point count changes at frame {}: expected {}, got {} (varying topology is not supported)",
+                frame, expected, actual
+            ),
+            AnimError::CorruptFrame(frame) => write!(f, "Frame {} payload is corrupt", frame),
+            AnimError::FrameOutOfRange { index, count } => {
+                write!(f, "Frame {} out of range ({} frames)", index, count)
+            }
+        }
+    }
+}
+
+impl std::error::Error for AnimError {}
+
+impl From<io::Error> for AnimError {
+    fn from(err: io::Error) -> Self {
+        AnimError::IoError(err)
+    }
+}
+
+impl From<AbcError> for AnimError {
+    fn from(err: AbcError) -> Self {
+        AnimError::Alembic(err)
+    }
+}
+
+/// Réglages du bake animé.
+/// Deux bakes des mêmes sources avec les mêmes réglages sont identiques au bit près.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct AnimSettings {
+    /// Erreur maximale tolérée sur une position (unités monde)
+    pub tolerance: f32,
+    /// Niveau zstd des payloads (1..=22)
+    pub compression_level: i32,
+}
+
+impl Default for AnimSettings {
+    fn default() -> Self {
+        Self { tolerance: 1e-4, compression_level: 3 }
+    }
+}
+
+/// Pas i16 couvrant `values` (0 si toutes nulles)
+fn quantization_step(values: impl Iterator<Item = f32>) -> f32 {
+    values.fold(0.0f32, |max, v| max.max(v.abs())) / i16::MAX as f32
+}
+
+fn quantize(value: f32, step: f32) -> i16 {
+    if step > 0.0 { (value / step).round().clamp(-(i16::MAX as f32), i16::MAX as f32) as i16 } else { 0 }
+}
+
+/// Octets rangés par plans : l'octet k de chaque élément, pour k = 0..size
+fn shuffle(bytes: &[u8], size: usize) -> Vec<u8> {
+    let count = bytes.len() / size;
+    let mut out = vec![0u8; bytes.len()];
+    for (i, element) in bytes.chunks_exact(size).enumerate() {
+        for (k, &b) in element.iter().enumerate() {
+            out[k * count + i] = b;
+        }
+    }
+    out
+}
+
+fn unshuffle(planes: &[u8], size: usize) -> Vec<u8> {
+    let count = planes.len() / size;
+    let mut out = vec![0u8; planes.len()];
+    for (k, plane) in planes.chunks_exact(count.max(1)).enumerate().take(size) {
+        for (i, &b) in plane.iter().enumerate() {
+            out[i * size + k] = b;
+        }
+    }
+    out
+}
+
+struct PendingFrame {
+    entry: FrameEntry,
+    bytes: Vec<u8>,
+}
+
+/// Assembleur de fichiers .seedanim.
+/// Les frames sont compressées dès `push_frame`, seul le résultat reste en mémoire.
+pub struct AnimWriter {
+    atom_count: u64,
+    fps: f32,
+    start_time: f64,
+    settings: AnimSettings,
+    content_hash: [u8; 32],
+    objects: Vec<SeedObject>,
+    frames: Vec<PendingFrame>,
+    /// Positions et index de la keyframe courante
+    keyframe: Option<(u32, Vec<f32>)>,
+    min: [f32; 3],
+    max: [f32; 3],
+}
+
+impl AnimWriter {
+    pub fn new(atom_count: u64, fps: f32, start_time: f64, settings: AnimSettings) -> Self {
+        Self {
+            atom_count,
+            fps,
+            start_time,
+            settings,
+            content_hash: [0; 32],
+            objects: Vec::new(),
+            frames: Vec::new(),
+            keyframe: None,
+            min: [f32::MAX; 3],
+            max: [f32::MIN; 3],
+        }
+    }
+
+    /// Empreinte des entrées du bake (voir `anim_bake_hash`)
+    pub fn set_content_hash(&mut self, hash: [u8; 32]) -> &mut Self {
+        self.content_hash = hash;
+        self
+    }
+
+    /// Plages de points par objet (AABB de la première frame)
+    pub fn set_objects(&mut self, objects: Vec<SeedObject>) -> &mut Self {
+        self.objects = objects;
+        self
+    }
+
+    /// Ajoute la frame suivante : `atom_count` positions xyz, vitesses optionnelles
+    pub fn push_frame(&mut self, positions: &[f32], velocities: Option<&[f32]>) -> Result<(), AnimError> {
+        let index = self.frames.len() as u32;
+        let len = positions.len() as u64 / 3;
+        if len != self.atom_count || !positions.len().is_multiple_of(3) {
+            return Err(AnimError::TopologyChanged { frame: index, expected: self.atom_count, actual: len });
+        }
+        for p in positions.chunks_exact(3) {
+            for ((min, max), &v) in self.min.iter_mut().zip(&mut self.max).zip(p) {
+                *min = min.min(v);
+                *max = max.max(v);
+            }
+        }
+
+        // Delta vers la keyframe courante, sauf si son pas dépasse la tolérance
+        // (l'erreur d'arrondi vaut au plus un demi-pas)
+        let delta = self.keyframe.as_ref().and_then(|(key, base)| {
+            let step = quantization_step(positions.iter().zip(base).map(|(p, b)| p - b));
+            (step * 0.5 <= self.settings.tolerance).then_some((*key, step, base))
+        });
+        let mut entry = FrameEntry::zeroed();
+        let mut raw = match delta {
+            Some((key, step, base)) => {
+                entry.keyframe = key;
+                entry.position_step = step;
+                let deltas: Vec<i16> = positions.iter().zip(base).map(|(p, b)| quantize(p - b, step)).collect();
+                shuffle(bytemuck::cast_slice(&deltas), 2)
+            }
+            None => {
+                entry.keyframe = index;
+                entry.flags |= FRAME_FLAG_KEY;
+                self.keyframe = Some((index, positions.to_vec()));
+                shuffle(bytemuck::cast_slice(positions), 4)
+            }
+        };
+        if let Some(velocities) = velocities.filter(|v| v.len() == positions.len()) {
+            let step = quantization_step(velocities.iter().copied());
+            let quantized: Vec<i16> = velocities.iter().map(|&v| quantize(v, step)).collect();
+            entry.flags |= FRAME_FLAG_VELOCITIES;
+            entry.velocity_step = step;
+            raw.extend(shuffle(bytemuck::cast_slice(&quantized), 2));
+        }
+
+        let bytes = zstd::stream::encode_all(raw.as_slice(), self.settings.compression_level)?;
+        entry.size = bytes.len() as u32;
+        entry.raw_size = raw.len() as u32;
+        entry.checksum = checksum(&bytes);
+        self.frames.push(PendingFrame { entry, bytes });
+        Ok(())
+    }
+
+    pub fn frame_count(&self) -> u32 {
+        self.frames.len() as u32
+    }
+
+    /// Nombre de keyframes émises jusqu'ici
+    pub fn keyframe_count(&self) -> u32 {
+        self.frames.iter().filter(|f| f.entry.is_key()).count() as u32
+    }
+
+    /// Écrit le conteneur complet sur disque (remplacement atomique : le runtime
+    /// garde le .seedanim mappé pendant toute la lecture)
+    pub fn write_to(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        write_atomically(path.as_ref(), |out| self.write(out))
+    }
+
+    pub fn write<W: Write>(&self, out: &mut W) -> io::Result<()> {
+        let all_velocities = !self.frames.is_empty() && self.frames.iter().all(|f| f.entry.has_velocities());
+        let empty = self.frames.is_empty() || self.atom_count == 0;
+        let header = AnimFileHeader {
+            magic: ANIM_MAGIC,
+            version: ANIM_VERSION,
+            atom_count: self.atom_count,
+            frame_count: self.frames.len() as u32,
+            fps: self.fps,
+            start_time: self.start_time,
+            flags: if all_velocities { ANIM_FLAG_VELOCITIES } else { 0 },
+            object_count: self.objects.len() as u32,
+            tolerance: self.settings.tolerance,
+            _padding: 0,
+            min: if empty { [0.0; 3] } else { self.min },
+            max: if empty { [0.0; 3] } else { self.max },
+            content_hash: self.content_hash,
+            _reserved: [0; 3],
+        };
+
+        // Payloads à la suite des tables, dans l'ordre des frames
+        let mut cursor = (std::mem::size_of::<AnimFileHeader>()
+            + self.objects.len() * std::mem::size_of::<SeedObject>()
+            + self.frames.len() * std::mem::size_of::<FrameEntry>()) as u64;
+        let entries: Vec<FrameEntry> = self.frames.iter()
+            .map(|frame| {
+                let entry = FrameEntry { offset: cursor, ..frame.entry };
+                cursor += entry.size as u64;
+                entry
+            })
+            .collect();
+
+        out.write_all(bytemuck::bytes_of(&header))?;
+        out.write_all(bytemuck::cast_slice(&self.objects))?;
+        out.write_all(bytemuck::cast_slice(&entries))?;
+        for frame in &self.frames {
+            out.write_all(&frame.bytes)?;
+        }
+        Ok(())
+    }
+}
+
+/// Frame décodée
+pub struct AnimFrame {
+    /// xyz par point
+    pub positions: Vec<f32>,
+    /// xyz par point, unités par seconde (None si la frame n'en porte pas)
+    pub velocities: Option<Vec<f32>>,
+}
+
+/// Fichier .seedanim ouvert et validé.
+/// Garde la dernière keyframe décodée : une lecture séquentielle ne décode
+/// qu'un payload par frame.
+pub struct AnimReader {
+    map: Mmap,
+    header: AnimFileHeader,
+    objects: Vec<SeedObject>,
+    frames: Vec<FrameEntry>,
+    keyframe: Option<(u32, Vec<f32>)>,
+}
+
+impl AnimReader {
+    /// Mappe et valide un .seedanim (magic, version, bornes des payloads)
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, AnimError> {
+        let file = File::open(path)?;
+        // SAFETY: les bakes ne réécrivent jamais un .seedanim en place : ils écrivent
+        // `<out>.tmp` puis le renomment (`write_atomically`), ce mapping garde
+        // l'ancien inode intact jusqu'à sa fermeture.
+        let map = unsafe { Mmap::map(&file)? };
+        let file_len = map.len() as u64;
+
+        let header_size = std::mem::size_of::<AnimFileHeader>();
+        if map.len() < header_size {
+            return Err(AnimError::Truncated { expected: header_size as u64, actual: file_len });
+        }
+        let header: AnimFileHeader = bytemuck::pod_read_unaligned(&map[..header_size]);
+        if header.magic != ANIM_MAGIC {
+            return Err(AnimError::BadMagic(header.magic));
+        }
+        if header.version != ANIM_VERSION {
+            return Err(AnimError::UnsupportedVersion(header.version));
+        }
+
+        let objects_end = header_size as u64 + header.object_count as u64 * std::mem::size_of::<SeedObject>() as u64;
+        let table_end = objects_end + header.frame_count as u64 * std::mem::size_of::<FrameEntry>() as u64;
+        if table_end > file_len {
+            return Err(AnimError::Truncated { expected: table_end, actual: file_len });
+        }
+        let objects: Vec<SeedObject> = map[header_size..objects_end as usize]
+            .chunks_exact(std::mem::size_of::<SeedObject>())
+            .map(bytemuck::pod_read_unaligned)
+            .collect();
+        let frames: Vec<FrameEntry> = map[objects_end as usize..table_end as usize]
+            .chunks_exact(std::mem::size_of::<FrameEntry>())
+            .map(bytemuck::pod_read_unaligned)
+            .collect();
+
+        for (i, entry) in frames.iter().enumerate() {
+            let end = entry.offset.saturating_add(entry.size as u64);
+            if end > file_len {
+                return Err(AnimError::Truncated { expected: end, actual: file_len });
+            }
+            let reference = frames.get(entry.keyframe as usize);
+            if !reference.is_some_and(FrameEntry::is_key) {
+                return Err(AnimError::CorruptFrame(i as u32));
+            }
+        }
+
+        Ok(Self { map, header, objects, frames, keyframe: None })
+    }
+
+    pub fn header(&self) -> &AnimFileHeader {
+        &self.header
+    }
+
+    pub fn atom_count(&self) -> u64 {
+        self.header.atom_count
+    }
+
+    pub fn frame_count(&self) -> u32 {
+        self.header.frame_count
+    }
+
+    pub fn fps(&self) -> f32 {
+        self.header.fps
+    }
+
+    /// Durée couverte, de la frame 0 à la dernière (secondes)
+    pub fn duration(&self) -> f64 {
+        self.header.frame_count.saturating_sub(1) as f64 / self.header.fps as f64
+    }
+
+    /// Temps (secondes) de la frame `index`
+    pub fn frame_time(&self, index: u32) -> f64 {
+        self.header.start_time + index as f64 / self.header.fps as f64
+    }
+
+    pub fn has_velocities(&self) -> bool {
+        self.header.flags & ANIM_FLAG_VELOCITIES != 0
+    }
+
+    /// Empreinte des sources et réglages du bake
+    pub fn content_hash(&self) -> [u8; 32] {
+        self.header.content_hash
+    }
+
+    /// Plages de points par objet
+    pub fn objects(&self) -> &[SeedObject] {
+        &self.objects
+    }
+
+    /// Table des frames
+    pub fn frames(&self) -> &[FrameEntry] {
+        &self.frames
+    }
+
+    /// Payload décodé et remis en ordre naturel : (positions ou deltas, vitesses)
+    fn decode_payload(&self, index: u32) -> Result<(Vec<u8>, Option<Vec<u8>>), AnimError> {
+        let entry = &self.frames[index as usize];
+        let stored = &self.map[entry.offset as usize..(entry.offset + entry.size as u64) as usize];
+        if checksum(stored) != entry.checksum {
+            return Err(AnimError::CorruptFrame(index));
+        }
+        let raw = zstd::stream::decode_all(stored)?;
+        let values = self.header.atom_count as usize * 3;
+        let position_size = if entry.is_key() { 4 } else { 2 };
+        let velocity_size = if entry.has_velocities() { 2 } else { 0 };
+        if raw.len() != entry.raw_size as usize || raw.len() != values * (position_size + velocity_size) {
+            return Err(AnimError::CorruptFrame(index));
+        }
+        let (positions, velocities) = raw.split_at(values * position_size);
+        Ok((unshuffle(positions, position_size), entry.has_velocities().then(|| unshuffle(velocities, 2))))
+    }
+
+    /// Décode la frame `index` (au plus deux payloads : sa keyframe et son delta)
+    pub fn frame(&mut self, index: u32) -> Result<AnimFrame, AnimError> {
+        let count = self.header.frame_count;
+        let entry = *self.frames.get(index as usize).ok_or(AnimError::FrameOutOfRange { index, count })?;
+
+        if self.keyframe.as_ref().is_none_or(|(key, _)| *key != entry.keyframe) {
+            let (bytes, _) = self.decode_payload(entry.keyframe)?;
+            let base = bytes.chunks_exact(4).map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]])).collect();
+            self.keyframe = Some((entry.keyframe, base));
+        }
+        let (bytes, velocities) = self.decode_payload(index)?;
+        let base = self.keyframe.as_ref().map(|(_, base)| base.as_slice()).unwrap_or_default();
+        let positions = if entry.is_key() {
+            base.to_vec()
+        } else {
+            base.iter().zip(bytes.chunks_exact(2))
+                .map(|(b, d)| b + i16::from_le_bytes([d[0], d[1]]) as f32 * entry.position_step)
+                .collect()
+        };
+        let velocities = velocities.map(|bytes| {
+            bytes.chunks_exact(2).map(|v| i16::from_le_bytes([v[0], v[1]]) as f32 * entry.velocity_step).collect()
+        });
+        Ok(AnimFrame { positions, velocities })
+    }
+}
+
+/// Empreinte blake3 d'un bake animé : version du format, contenu de la source, réglages
+pub fn anim_bake_hash(path: &str, settings: &AnimSettings) -> [u8; 32] {
+    let mut hasher = blake3::Hasher::new();
+    hasher.update(&ANIM_VERSION.to_le_bytes());
+    hasher.update(env!("CARGO_PKG_VERSION").as_bytes());
+    let bytes = std::fs::read(path).unwrap_or_default();
+    hasher.update(&(bytes.len() as u64).to_le_bytes());
+    hasher.update(&bytes);
+    hasher.update(format!("{:?}", settings).as_bytes());
+    *hasher.finalize().as_bytes()
+}
+
+/// Cache Alembic -> .seedanim : toutes les frames de l'archive, à sa cadence.
+/// Le nombre de points doit rester constant.
+pub fn bake_alembic(path: &str, output_path: &str, settings: &AnimSettings) -> Result<(), AnimError> {
+    let stream = AbcStream::open(path)?;
+    let first = stream.load_frame(0)?;
+    let atom_count = first.positions.len() as u64 / 3;
+    info!(
+        "🎞️ Alembic {} : {} frames à {} fps, {} points par frame",
+        path, stream.frame_count, stream.fps, atom_count
+    );
+
+    // Une plage par objet géométrique, AABB de la première frame
+    let mut objects = Vec::new();
+    let mut first_atom = 0u64;
+    for &(object, count) in &first.object_counts {
+        let points = &first.positions[first_atom as usize * 3..(first_atom + count as u64) as usize * 3];
+        let (min, max) = points.chunks_exact(3).fold(([f32::MAX; 3], [f32::MIN; 3]), |(min, max), p| {
+            ([min[0].min(p[0]), min[1].min(p[1]), min[2].min(p[2])], [max[0].max(p[0]), max[1].max(p[1]), max[2].max(p[2])])
+        });
+        let (min, max) = if count == 0 { ([0.0; 3], [0.0; 3]) } else { (min, max) };
+        objects.push(SeedObject::new(&stream.objects()[object].name, first_atom, count as u64, 0, min, max));
+        first_atom += count as u64;
+    }
+
+    let mut writer = AnimWriter::new(atom_count, stream.fps, stream.start_time, settings.clone());
+    writer.set_objects(objects).set_content_hash(anim_bake_hash(path, settings));
+    writer.push_frame(&first.positions, first.velocities.as_deref())?;
+    for index in 1..stream.frame_count {
+        let frame = stream.load_frame(index)?;
+        writer.push_frame(&frame.positions, frame.velocities.as_deref())?;
+    }
+    writer.write_to(output_path)?;
+    info!(
+        "✅ {} : {} frames dont {} keyframes (tolérance {})",
+        output_path, writer.frame_count(), writer.keyframe_count(), settings.tolerance
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ATOMS: usize = 64;
+
+    /// Nuage qui dérive lentement, avec un saut à la frame `jump` (nouvelle keyframe)
+    fn positions(frame: usize, jump: usize) -> Vec<f32> {
+        let offset = if frame >= jump { 100.0 } else { 0.0 };
+        (0..ATOMS * 3).map(|i| (i as f32 * 0.37).sin() + frame as f32 * 1e-3 + offset).collect()
+    }
+
+    fn velocities(frame: usize) -> Vec<f32> {
+        (0..ATOMS * 3).map(|i| (i as f32 * 0.11 + frame as f32).cos()).collect()
+    }
+
+    fn temp_path(name: &str) -> std::path::PathBuf {
+        std::env::temp_dir().join(format!("seed_architect_{}_{}.seedanim", name, std::process::id()))
+    }
+
+    #[test]
+    fn shuffle_round_trip() {
+        let bytes: Vec<u8> = (0..=255).cycle().take(4 * 37).collect();
+        for size in [1, 2, 4] {
+            assert_eq!(unshuffle(&shuffle(&bytes, size), size), bytes);
+        }
+    }
+
+    #[test]
+    fn round_trip_stays_within_tolerance() {
+        let settings = AnimSettings { tolerance: 1e-3, ..Default::default() };
+        let (count, jump) = (12, 7);
+        let mut writer = AnimWriter::new(ATOMS as u64, 24.0, 0.5, settings.clone());
+        for frame in 0..count {
+            // Vitesses sur toutes les frames sauf une, et une de longueur incohérente
+            let v = velocities(frame);
+            let v = match frame {
+                3 => None,
+                5 => Some(&v[..3]),
+                _ => Some(&v[..]),
+            };
+            writer.push_frame(&positions(frame, jump), v).unwrap();
+        }
+        assert_eq!(writer.keyframe_count(), 2);
+
+        let path = temp_path("round_trip");
+        writer.write_to(&path).unwrap();
+        let mut reader = AnimReader::open(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(reader.frame_count(), count as u32);
+        assert_eq!(reader.atom_count(), ATOMS as u64);
+        // Toutes les frames n'ont pas de vitesses : pas de flag global (slots de 3 floats)
+        assert!(!reader.has_velocities());
+        assert!(reader.frames()[0].is_key() && reader.frames()[jump].is_key());
+        assert!(reader.frames()[jump + 1..].iter().all(|e| e.keyframe == jump as u32));
+
+        // Ordre quelconque : la keyframe de référence est redécodée au besoin
+        for frame in [0, 4, 11, 2, 7, 3, 5, 6] {
+            let decoded = reader.frame(frame as u32).unwrap();
+            let error = decoded.positions.iter().zip(positions(frame, jump))
+                .map(|(a, b)| (a - b).abs())
+                .fold(0.0, f32::max);
+            assert!(error <= settings.tolerance, "frame {} : erreur {}", frame, error);
+
+            match (frame, decoded.velocities) {
+                (3 | 5, v) => assert!(v.is_none(), "frame {}", frame),
+                (_, Some(v)) => {
+                    let step = reader.frames()[frame].velocity_step;
+                    assert!(v.iter().zip(velocities(frame)).all(|(a, b)| (a - b).abs() <= step * 0.5 + 1e-6));
+                }
+                (_, None) => panic!("frame {} sans vitesses", frame),
+            }
+        }
+        assert!(matches!(reader.frame(count as u32), Err(AnimError::FrameOutOfRange { .. })));
+    }
+
+    #[test]
+    fn velocity_flag_requires_every_frame() {
+        let mut writer = AnimWriter::new(ATOMS as u64, 30.0, 0.0, AnimSettings::default());
+        for frame in 0..3 {
+            writer.push_frame(&positions(frame, usize::MAX), Some(&velocities(frame))).unwrap();
+        }
+        let path = temp_path("velocities");
+        writer.write_to(&path).unwrap();
+        let reader = AnimReader::open(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert!(reader.has_velocities());
+    }
+
+    #[test]
+    fn rejects_topology_change() {
+        let mut writer = AnimWriter::new(ATOMS as u64, 30.0, 0.0, AnimSettings::default());
+        let result = writer.push_frame(&[0.0; 6], None);
+        assert!(matches!(result, Err(AnimError::TopologyChanged { frame: 0, expected: 64, actual: 2 })));
+    }
+}
@@ -0,0 +1,376 @@
+// crates/seed_architect/src/asset_db.rs
+//
+// Base des assets bakés (assets/processed/assets.json) : pour chaque fichier
+// produit, la source, ses dépendances (.mtl, textures, .bin glTF...) avec leur
+// empreinte blake3, les réglages du bake, la version du baker et l'empreinte
+// écrite dans l'en-tête du fichier produit.
+//
+// Un asset est périmé dès que l'un de ces éléments ne correspond plus : seul
+// celui-là est re-baké. Les fichiers produits enregistrés dont la source a
+// disparu sont des orphelins, supprimables par le GC. Un fichier que la base ne
+// connaît pas n'est jamais supprimé sans le demander (`--gc-untracked`), et pas
+// du tout quand la base vient d'être reconstruite : elle ne sait alors plus rien.
+//
+// Les chemins des sources et des dépendances sont enregistrés sous forme
+// canonique : `assets/raw/a.obj` et `./assets/raw/a.obj` sont la même source.
+
+use crate::abc_loader::AbcStream;
+use crate::anim::{bake_alembic, AnimError, AnimReader, AnimSettings, ANIM_VERSION};
+use crate::format::SEED_VERSION;
+use crate::importer::{BakeSettings, SeedImporter};
+use crate::reader::SeedReader;
+use log::warn;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Base par défaut, à côté des fichiers produits
+pub const ASSET_DATABASE: &str = "assets/processed/assets.json";
+
+/// Version du format JSON de la base
+pub const ASSET_DATABASE_VERSION: u32 = 1;
+
+/// Extensions des fichiers produits (candidats au GC)
+pub const OUTPUT_EXTENSIONS: [&str; 2] = ["seed", "seedanim"];
+
+#[derive(Debug)]
+pub enum AssetDbError {
+    IoError(io::Error),
+    Json(serde_json::Error),
+}
+
+impl fmt::Display for AssetDbError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AssetDbError::IoError(e) => write!(f, "IO Error: {}", e),
+            AssetDbError::Json(e) => write!(f, "Invalid asset database: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for AssetDbError {}
+
+impl From<io::Error> for AssetDbError {
+    fn from(err: io::Error) -> Self {
+        AssetDbError::IoError(err)
+    }
+}
+
+impl From<serde_json::Error> for AssetDbError {
+    fn from(err: serde_json::Error) -> Self {
+        AssetDbError::Json(err)
+    }
+}
+
+/// Réglages d'un bake, selon le type de source
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub enum AssetSettings {
+    /// Source statique -> .seed
+    Seed(BakeSettings),
+    /// Alembic -> .seedanim
+    Anim(AnimSettings),
+}
+
+impl AssetSettings {
+    /// Réglages applicables à `source` (Alembic ou importeurs)
+    pub fn for_source(source: &str, bake: &BakeSettings, anim: &AnimSettings) -> Self {
+        if AbcStream::handles(source) {
+            AssetSettings::Anim(anim.clone())
+        } else {
+            AssetSettings::Seed(bake.clone())
+        }
+    }
+
+    /// Extension du fichier produit
+    pub fn output_extension(&self) -> &'static str {
+        match self {
+            AssetSettings::Seed(_) => "seed",
+            AssetSettings::Anim(_) => "seedanim",
+        }
+    }
+
+    /// Bake `source` vers `output`. Les importeurs statiques paniquent sur une
+    /// source invalide : l'appelant qui doit survivre à un échec les isole.
+    pub fn bake(&self, source: &str, output: &str) -> Result<(), AnimError> {
+        match self {
+            AssetSettings::Seed(settings) => {
+                SeedImporter::import_and_bake_with(source, output, settings);
+                Ok(())
+            }
+            AssetSettings::Anim(settings) => bake_alembic(source, output, settings),
+        }
+    }
+}
+
+/// Fichier suivi et son empreinte blake3 (None : absent au moment du bake)
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct FileHash {
+    pub path: String,
+    pub hash: Option<String>,
+}
+
+impl FileHash {
+    /// Empreinte de `path`, enregistré sous sa forme canonique
+    pub fn of(path: &Path) -> Self {
+        let hash = std::fs::read(path).ok().map(|bytes| blake3::hash(&bytes).to_hex().to_string());
+        Self { path: normalize(path).to_string_lossy().into_owned(), hash }
+    }
+}
+
+/// Empreintes d'une source et de ses dépendances, prises avant le bake : une
+/// modification pendant le bake laisse l'asset périmé au lieu d'être masquée
+#[derive(Clone, Debug)]
+pub struct SourceSnapshot {
+    pub source: FileHash,
+    pub dependencies: Vec<FileHash>,
+}
+
+impl SourceSnapshot {
+    pub fn of(source: &str) -> Self {
+        Self {
+            source: FileHash::of(Path::new(source)),
+            dependencies: dependencies(source).iter().map(|dep| FileHash::of(dep)).collect(),
+        }
+    }
+}
+
+/// Un fichier produit et tout ce dont il dépend
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct AssetRecord {
+    pub output: String,
+    pub source: FileHash,
+    pub dependencies: Vec<FileHash>,
+    pub settings: AssetSettings,
+    /// `baker_version()` au moment du bake
+    pub baker: String,
+    /// Empreinte de l'en-tête du fichier produit (hex)
+    pub content_hash: String,
+}
+
+/// Raison pour laquelle un asset doit être re-baké
+#[derive(Clone, Debug, PartialEq)]
+pub enum Staleness {
+    NotRecorded,
+    OutputMissing,
+    /// Le fichier produit n'est plus celui du dernier bake
+    OutputReplaced,
+    SettingsChanged,
+    BakerChanged,
+    SourceChanged,
+    /// Dépendance ajoutée ou retirée (nouveau `mtllib`, texture renommée...)
+    DependenciesChanged,
+    DependencyChanged(String),
+}
+
+impl fmt::Display for Staleness {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Staleness::NotRecorded => write!(f, "not recorded"),
+            Staleness::OutputMissing => write!(f, "output missing"),
+            Staleness::OutputReplaced => write!(f, "output replaced"),
+            Staleness::SettingsChanged => write!(f, "settings changed"),
+            Staleness::BakerChanged => write!(f, "baker changed"),
+            Staleness::SourceChanged => write!(f, "source changed"),
+            Staleness::DependenciesChanged => write!(f, "dependencies added or removed"),
+            Staleness::DependencyChanged(path) => write!(f, "dependency changed: {}", path),
+        }
+    }
+}
+
+/// Version du baker et des formats écrits : un changement invalide tous les bakes
+pub fn baker_version() -> String {
+    format!("seed_architect {} (seed v{}, seedanim v{})", env!("CARGO_PKG_VERSION"), SEED_VERSION, ANIM_VERSION)
+}
+
+/// Fichiers dont dépend une source (aucun pour un Alembic : tout est dans le .abc)
+pub fn dependencies(source: &str) -> Vec<PathBuf> {
+    if AbcStream::handles(source) {
+        Vec::new()
+    } else {
+        SeedImporter::dependencies(source)
+    }
+}
+
+/// Empreinte lue dans l'en-tête d'un .seed / .seedanim
+pub fn output_content_hash(output: &Path) -> Option<[u8; 32]> {
+    let is_anim = output.extension().is_some_and(|e| e.eq_ignore_ascii_case("seedanim"));
+    if is_anim {
+        AnimReader::open(output).ok().map(|r| r.content_hash())
+    } else {
+        SeedReader::open(output).ok().map(|r| r.content_hash())
+    }
+}
+
+fn hex(bytes: &[u8; 32]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Contenu du fichier JSON
+#[derive(Serialize, Deserialize)]
+struct DatabaseFile {
+    version: u32,
+    assets: Vec<AssetRecord>,
+}
+
+/// Base des assets, indexée par fichier produit
+pub struct AssetDatabase {
+    path: PathBuf,
+    records: BTreeMap<String, AssetRecord>,
+    /// Base existante mais illisible ou d'une autre version, repartie de zéro
+    reset: bool,
+}
+
+impl AssetDatabase {
+    /// Base vide, écrite à `path` au premier `save`
+    pub fn new(path: impl AsRef<Path>) -> Self {
+        Self { path: path.as_ref().to_path_buf(), records: BTreeMap::new(), reset: false }
+    }
+
+    /// Base vide remplaçant celle de `path`, illisible : le GC est refusé
+    pub fn reset(path: impl AsRef<Path>) -> Self {
+        Self { reset: true, ..Self::new(path) }
+    }
+
+    /// Vrai si la base a été reconstruite à l'ouverture : ce qu'elle ignore
+    /// n'est pas pour autant orphelin
+    pub fn was_reset(&self) -> bool {
+        self.reset
+    }
+
+    /// Charge la base (vide si le fichier n'existe pas encore).
+    /// Une base d'une autre version est ignorée : tout sera re-baké.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, AssetDbError> {
+        let mut db = Self::new(path);
+        let text = match std::fs::read_to_string(&db.path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(db),
+            Err(e) => return Err(e.into()),
+        };
+        let file: serde_json::Value = serde_json::from_str(&text)?;
+        if file.get("version").and_then(|v| v.as_u64()) != Some(ASSET_DATABASE_VERSION as u64) {
+            warn!("⚠️ Base d'assets {} d'une autre version : ignorée", db.path.display());
+            db.reset = true;
+            return Ok(db);
+        }
+        let file: DatabaseFile = serde_json::from_value(file)?;
+        db.records = file.assets.into_iter().map(|r| (r.output.clone(), r)).collect();
+        Ok(db)
+    }
+
+    /// Écrit la base (fichier temporaire puis renommage : jamais de base à moitié écrite)
+    pub fn save(&self) -> Result<(), AssetDbError> {
+        let file = DatabaseFile { version: ASSET_DATABASE_VERSION, assets: self.records.values().cloned().collect() };
+        if let Some(dir) = self.path.parent().filter(|d| !d.as_os_str().is_empty()) {
+            std::fs::create_dir_all(dir)?;
+        }
+        let tmp = self.path.with_extension("json.tmp");
+        std::fs::write(&tmp, serde_json::to_string_pretty(&file)?)?;
+        std::fs::rename(&tmp, &self.path)?;
+        Ok(())
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn records(&self) -> impl Iterator<Item = &AssetRecord> {
+        self.records.values()
+    }
+
+    pub fn get(&self, output: &str) -> Option<&AssetRecord> {
+        self.records.get(output)
+    }
+
+    /// Pourquoi `output` doit être re-baké depuis `source` avec `settings` (None : à jour)
+    pub fn staleness(&self, source: &str, output: &str, settings: &AssetSettings) -> Option<Staleness> {
+        let Some(record) = self.records.get(output) else {
+            return Some(Staleness::NotRecorded);
+        };
+        let Some(content_hash) = output_content_hash(Path::new(output)) else {
+            return Some(Staleness::OutputMissing);
+        };
+        if record.settings != *settings {
+            return Some(Staleness::SettingsChanged);
+        }
+        if record.baker != baker_version() {
+            return Some(Staleness::BakerChanged);
+        }
+        if record.source != FileHash::of(Path::new(source)) {
+            return Some(Staleness::SourceChanged);
+        }
+
+        let current = dependencies(source);
+        let same_set = current.len() == record.dependencies.len()
+            && current.iter().zip(&record.dependencies).all(|(path, dep)| *normalize(path).to_string_lossy() == dep.path);
+        if !same_set {
+            return Some(Staleness::DependenciesChanged);
+        }
+        if let Some(dep) = record.dependencies.iter().find(|dep| FileHash::of(Path::new(&dep.path)) != **dep) {
+            return Some(Staleness::DependencyChanged(dep.path.clone()));
+        }
+
+        (hex(&content_hash) != record.content_hash).then_some(Staleness::OutputReplaced)
+    }
+
+    /// Enregistre un bake réussi : empreintes de la source et des dépendances prises
+    /// avant le bake (`SourceSnapshot::of`), empreinte actuelle du fichier produit
+    pub fn record(&mut self, snapshot: SourceSnapshot, output: &str, settings: &AssetSettings) {
+        let content_hash = output_content_hash(Path::new(output)).map(|h| hex(&h)).unwrap_or_default();
+        let record = AssetRecord {
+            output: output.to_string(),
+            source: snapshot.source,
+            dependencies: snapshot.dependencies,
+            settings: settings.clone(),
+            baker: baker_version(),
+            content_hash,
+        };
+        self.records.insert(output.to_string(), record);
+    }
+
+    pub fn remove(&mut self, output: &str) -> Option<AssetRecord> {
+        self.records.remove(output)
+    }
+
+    /// Fichiers produits de `out_dir` (sous-dossiers compris) enregistrés avec une
+    /// source disparue. `untracked` y ajoute ceux que la base ne connaît pas.
+    /// Toujours vide pour une base reconstruite : elle ne connaît plus rien.
+    pub fn orphans(&self, out_dir: &Path, untracked: bool) -> Vec<PathBuf> {
+        if self.reset {
+            return Vec::new();
+        }
+        // (sortie, source encore présente)
+        let recorded: Vec<(PathBuf, bool)> = self.records.values()
+            .map(|r| (normalize(Path::new(&r.output)), Path::new(&r.source.path).is_file()))
+            .collect();
+        let mut outputs = Vec::new();
+        collect_outputs(out_dir, &mut outputs);
+        outputs.retain(|output| {
+            let output = normalize(output);
+            let mut records = recorded.iter().filter(|(o, _)| *o == output).peekable();
+            match records.peek() {
+                None => untracked,
+                Some(_) => records.all(|(_, live)| !live),
+            }
+        });
+        outputs
+    }
+}
+
+/// Chemin comparable (`./a.seed` == `a.seed`), canonique si le fichier existe
+fn normalize(path: &Path) -> PathBuf {
+    path.canonicalize().unwrap_or_else(|_| path.components().collect())
+}
+
+fn collect_outputs(dir: &Path, outputs: &mut Vec<PathBuf>) {
+    let Ok(entries) = std::fs::read_dir(dir) else { return };
+    for path in entries.filter_map(Result::ok).map(|e| e.path()) {
+        if path.is_dir() {
+            collect_outputs(&path, outputs);
+        } else if path.extension().and_then(|e| e.to_str()).is_some_and(|e| OUTPUT_EXTENSIONS.contains(&e)) {
+            outputs.push(path);
+        }
+    }
+}
@@ -0,0 +1,171 @@
+// crates/seed_architect/src/assimp_importer.rs
+//
+// Ingestion générique via Assimp (russimp) : FBX, Collada, 3DS, Blend...
+// Même scène brute que l'OBJ et le glTF (`RawScene`), même bake ensuite.
+// Assimp triangule et sépare les primitives ; on garde les triangles et on
+// aplatit la hiérarchie de nœuds en espace monde.
+
+use crate::importer::{BakeSettings, MaterialData, MeshData, RawScene, SeedImporter};
+use crate::texture::{srgb_to_linear, MaterialTextures, Texture};
+use glam::{Mat4, Vec2, Vec3};
+use log::warn;
+use russimp::material::Material;
+use russimp::node::Node;
+use russimp::scene::{PostProcess, Scene};
+use russimp::texture::{DataContent, TextureType};
+use std::cell::RefCell;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+use std::sync::Mutex;
+use std::time::SystemTime;
+
+/// Extensions confiées à Assimp (OBJ et glTF ont leurs propres importeurs)
+pub const ASSIMP_EXTENSIONS: [&str; 22] = [
+    "fbx", "dae", "3ds", "blend", "stl", "x", "ase", "lwo", "lws", "ms3d", "md2",
+    "md3", "md5mesh", "ac", "off", "dxf", "ifc", "x3d", "3mf", "b3d", "smd", "ogex",
+];
+
+/// Textures de couleur, par priorité (PBR puis classique)
+const COLOR_TEXTURES: [TextureType; 2] = [TextureType::BaseColor, TextureType::Diffuse];
+
+/// (source, (taille, date), dépendances)
+type DependencyCacheEntry = (PathBuf, (u64, SystemTime), Vec<PathBuf>);
+
+pub struct AssimpImporter;
+
+impl AssimpImporter {
+    pub fn import_and_bake(path: &str, output_path: &str) {
+        Self::import_and_bake_with(path, output_path, &BakeSettings::default());
+    }
+
+    pub fn import_and_bake_with(path: &str, output_path: &str, settings: &BakeSettings) {
+        let raw = Self::load_raw_assimp(path, settings.crease_angle);
+        SeedImporter::bake(&raw, path, output_path, settings);
+    }
+
+    pub fn handles(path: &str) -> bool {
+        Path::new(path).extension()
+            .and_then(|e| e.to_str())
+            .is_some_and(|e| ASSIMP_EXTENSIONS.iter().any(|x| e.eq_ignore_ascii_case(x)))
+    }
+
+    /// Textures externes référencées par les matériaux (les `*N` sont embarquées).
+    /// Lister les textures demande un import complet (FBX, Blend binaires) : le
+    /// résultat est mémorisé tant que la taille et la date de la source ne changent pas.
+    pub fn dependencies(path: &str) -> Vec<PathBuf> {
+        static CACHE: Mutex<Vec<DependencyCacheEntry>> = Mutex::new(Vec::new());
+
+        let Some(stamp) = std::fs::metadata(path).ok().and_then(|m| Some((m.len(), m.modified().ok()?))) else {
+            return Self::scan_dependencies(path);
+        };
+        let key = PathBuf::from(path);
+        let mut cache = CACHE.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some((_, _, dependencies)) = cache.iter().find(|(p, s, _)| *p == key && *s == stamp) {
+            return dependencies.clone();
+        }
+        let dependencies = Self::scan_dependencies(path);
+        cache.retain(|(p, _, _)| *p != key);
+        cache.push((key, stamp, dependencies.clone()));
+        dependencies
+    }
+
+    fn scan_dependencies(path: &str) -> Vec<PathBuf> {
+        let dir = Path::new(path).parent().unwrap_or(Path::new(""));
+        let Ok(scene) = Scene::from_file(path, vec![]) else {
+            return Vec::new();
+        };
+        let mut dependencies: Vec<PathBuf> = scene.materials.iter()
+            .flat_map(|m| m.textures.values().flatten())
+            .filter(|t| !t.path.is_empty() && !t.path.starts_with('*'))
+            .map(|t| dir.join(&t.path))
+            .collect();
+        dependencies.sort();
+        dependencies.dedup();
+        dependencies
+    }
+
+    pub(crate) fn load_raw_assimp(path: &str, crease_angle: f32) -> RawScene {
+        // Normales non générées par Assimp : même lissage (angle de pli) que les autres formats
+        let scene = Scene::from_file(path, vec![
+            PostProcess::Triangulate,
+            PostProcess::SortByPrimitiveType,
+            PostProcess::JoinIdenticalVertices,
+        ]).unwrap_or_else(|e| panic!("❌ Erreur Ingestion Assimp {} : {}", path, e));
+
+        let dir = Path::new(path).parent().unwrap_or(Path::new(""));
+        let mut raw = RawScene::default();
+        for material in &scene.materials {
+            raw.materials.push(MaterialData::from_assimp(material));
+            raw.textures.push(material_textures(material, dir));
+        }
+        if raw.materials.is_empty() {
+            raw.materials.push(MaterialData::GOLD);
+            raw.textures.push(MaterialTextures::default());
+        }
+
+        let Some(root) = scene.root.clone() else {
+            warn!("⚠️ {} ne contient aucun nœud", path);
+            return raw;
+        };
+        let mut stack: Vec<(Rc<RefCell<Node>>, Mat4)> = vec![(root, Mat4::IDENTITY)];
+        while let Some((node, parent)) = stack.pop() {
+            let node = node.borrow();
+            let t = &node.transformation; // Ligne par ligne côté Assimp
+            let world = parent * Mat4::from_cols_array_2d(&[
+                [t.a1, t.b1, t.c1, t.d1],
+                [t.a2, t.b2, t.c2, t.d2],
+                [t.a3, t.b3, t.c3, t.d3],
+                [t.a4, t.b4, t.c4, t.d4],
+            ]);
+            stack.extend(node.children.iter().map(|child| (child.clone(), world)));
+
+            for &id in &node.meshes {
+                let Some(mesh) = scene.meshes.get(id as usize) else { continue };
+                let material = (mesh.material_index as usize).min(raw.materials.len() - 1) as u32;
+                let name = if node.name.is_empty() { mesh.name.clone() } else { node.name.clone() };
+
+                // Points et segments séparés par SortByPrimitiveType : ignorés
+                let indices: Vec<u32> = mesh.faces.iter().filter(|f| f.0.len() == 3).flat_map(|f| f.0.iter().copied()).collect();
+                if indices.is_empty() {
+                    continue;
+                }
+                let uv_set = scene.materials.get(material as usize)
+                    .and_then(|m| COLOR_TEXTURES.iter().find_map(|k| m.textures.get(k)?.first()))
+                    .map_or(0, |t| t.uv_index as usize);
+                let data = MeshData {
+                    positions: mesh.vertices.iter().map(|v| Vec3::new(v.x, v.y, v.z)).collect(),
+                    normals: (!mesh.normals.is_empty()).then(|| mesh.normals.iter().map(|n| Vec3::new(n.x, n.y, n.z)).collect()),
+                    uvs: mesh.texture_coords.get(uv_set).cloned().flatten()
+                        .map(|uvs| uvs.iter().map(|uv| Vec2::new(uv.x, uv.y)).collect()),
+                    // Couleurs de sommet FBX / PLY / Collada en sRGB : "color" est linéaire
+                    colors: mesh.colors.first().cloned().flatten()
+                        .map(|colors| colors.iter().map(|c| Vec3::from([c.r, c.g, c.b].map(srgb_to_linear))).collect()),
+                    indices,
+                };
+                raw.add_mesh(name, material, data, world, crease_angle);
+            }
+        }
+        raw
+    }
+}
+
+/// Textures couleur / rugosité / métal d'un matériau Assimp, embarquées ou externes
+fn material_textures(material: &Material, dir: &Path) -> MaterialTextures {
+    let load = |kinds: &[TextureType]| {
+        let texture = kinds.iter().find_map(|k| material.textures.get(k)?.first())?;
+        match &texture.data {
+            Some(DataContent::Bytes(bytes)) => Texture::decode(bytes, &texture.path),
+            Some(DataContent::Texel(texels)) => Some(Texture::new(
+                texture.width,
+                texture.height,
+                texels.iter().map(|t| [t.r, t.g, t.b, t.a].map(|c| c as f32 / 255.0)).collect(),
+            )),
+            None => Texture::load(&dir.join(&texture.path)),
+        }
+    };
+    MaterialTextures {
+        diffuse: load(&COLOR_TEXTURES).map(Texture::into_linear), // sRGB par convention
+        roughness: load(&[TextureType::Roughness]),
+        metallic: load(&[TextureType::Metalness]),
+    }
+}
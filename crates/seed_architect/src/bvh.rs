@@ -0,0 +1,157 @@
+// crates/seed_architect/src/bvh.rs
+//
+// LBVH (Karras 2012) construit sur les atomes déjà triés par clé spatiale :
+// chaque nœud coupe sa plage d'atomes au bit le plus fort qui change entre
+// la première et la dernière clé. Pas de SAH, pas de tri supplémentaire.
+//
+// Layout plat, identique côté GLSL (std430, 32 octets) :
+//   - nœud interne : `count == 0`, enfants en `left_first` et `left_first + 1`
+//   - feuille      : atomes `left_first .. left_first + count`
+// La racine est le nœud 0.
+
+use bytemuck::{Pod, Zeroable};
+use glam::Vec3;
+
+/// Atomes maximum par feuille
+pub const LEAF_SIZE: usize = 4;
+
+/// Pile de parcours de picker.comp (`BVH_STACK_SIZE`). Un parcours en profondeur
+/// qui empile les deux enfants occupe au plus `profondeur + 1` entrées.
+pub const GPU_STACK_SIZE: usize = 64;
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, Pod, Zeroable)]
+pub struct BvhNode {
+    pub min: [f32; 3],
+    pub left_first: u32,
+    pub max: [f32; 3],
+    pub count: u32,
+}
+
+impl BvhNode {
+    pub fn is_leaf(&self) -> bool {
+        self.count > 0
+    }
+
+    /// Le rayon entre-t-il dans la boîte gonflée de `radius` avant `t_max` ? (slab test)
+    fn hit(&self, origin: Vec3, inv_dir: Vec3, radius: f32, t_max: f32) -> bool {
+        let t0 = (Vec3::from(self.min) - radius - origin) * inv_dir;
+        let t1 = (Vec3::from(self.max) + radius - origin) * inv_dir;
+        let near = t0.min(t1).max_element().max(0.0);
+        let far = t0.max(t1).min_element();
+        near <= far && near < t_max
+    }
+}
+
+/// Construit le LBVH. `keys` = clés des atomes, dans l'ordre (croissant) des atomes.
+/// Sur 128 bits pour préfixer la clé spatiale par l'objet : la coupe de plus haut
+/// niveau sépare alors les objets avant l'espace.
+pub fn build_lbvh(atoms: &[[f32; 6]], keys: &[u128]) -> Vec<BvhNode> {
+    debug_assert_eq!(atoms.len(), keys.len());
+    if atoms.is_empty() {
+        return Vec::new();
+    }
+
+    let mut nodes = vec![BvhNode::default()];
+    let mut stack = vec![(0usize, 0usize, atoms.len() - 1)];
+    while let Some((node, first, last)) = stack.pop() {
+        let (min, max) = atoms[first..=last].iter().fold(
+            (Vec3::splat(f32::MAX), Vec3::splat(f32::MIN)),
+            |(lo, hi), a| {
+                let p = Vec3::new(a[0], a[1], a[2]);
+                (lo.min(p), hi.max(p))
+            },
+        );
+        nodes[node].min = min.to_array();
+        nodes[node].max = max.to_array();
+
+        if last - first < LEAF_SIZE {
+            nodes[node].left_first = first as u32;
+            nodes[node].count = (last - first + 1) as u32;
+            continue;
+        }
+
+        let split = find_split(keys, first, last);
+        let left = nodes.len();
+        nodes.push(BvhNode::default());
+        nodes.push(BvhNode::default());
+        nodes[node].left_first = left as u32;
+        stack.push((left, first, split));
+        stack.push((left + 1, split + 1, last));
+    }
+    nodes
+}
+
+/// Profondeur de l'arbre (0 = racine seule). Les clés préfixées par l'objet
+/// (128 bits) et les positions dupliquées peuvent dépasser la pile du GPU.
+pub fn lbvh_depth(nodes: &[BvhNode]) -> usize {
+    if nodes.is_empty() {
+        return 0;
+    }
+    let mut deepest = 0;
+    let mut stack = vec![(0usize, 0usize)];
+    while let Some((node, depth)) = stack.pop() {
+        deepest = deepest.max(depth);
+        if !nodes[node].is_leaf() {
+            let left = nodes[node].left_first as usize;
+            stack.push((left, depth + 1));
+            stack.push((left + 1, depth + 1));
+        }
+    }
+    deepest
+}
+
+/// Dernier index de la moitié gauche : bit le plus fort qui diffère dans la plage
+fn find_split(keys: &[u128], first: usize, last: usize) -> usize {
+    let first_key = keys[first];
+    let last_key = keys[last];
+    if first_key == last_key {
+        return (first + last) / 2; // Clés identiques : coupe au milieu
+    }
+
+    // Recherche dichotomique du dernier atome partageant plus que le préfixe commun
+    let common = (first_key ^ last_key).leading_zeros();
+    let mut split = first;
+    let mut step = last - first;
+    loop {
+        step = step.div_ceil(2);
+        let candidate = split + step;
+        if candidate < last && (first_key ^ keys[candidate]).leading_zeros() > common {
+            split = candidate;
+        }
+        if step <= 1 {
+            break;
+        }
+    }
+    split
+}
+
+/// Lancer de rayon (même critère que picker.comp) : atome le plus proche le long
+/// du rayon parmi ceux à moins de `radius` de la droite. Renvoie (index, t).
+pub fn raycast(nodes: &[BvhNode], atoms: &[[f32; 6]], origin: Vec3, dir: Vec3, radius: f32) -> Option<(u32, f32)> {
+    let root = nodes.first()?;
+    let inv_dir = dir.recip();
+    let mut best: Option<(u32, f32)> = None;
+    let mut stack = vec![root];
+
+    while let Some(node) = stack.pop() {
+        if !node.hit(origin, inv_dir, radius, best.map_or(f32::MAX, |b| b.1)) {
+            continue;
+        }
+        if node.is_leaf() {
+            for i in node.left_first..node.left_first + node.count {
+                let a = &atoms[i as usize];
+                let p = Vec3::new(a[0], a[1], a[2]);
+                let t = (p - origin).dot(dir);
+                if t > 0.0 && t < best.map_or(f32::MAX, |b| b.1) && (origin + t * dir).distance(p) < radius {
+                    best = Some((i, t));
+                }
+            }
+        } else {
+            let left = node.left_first as usize;
+            stack.push(&nodes[left]);
+            stack.push(&nodes[left + 1]);
+        }
+    }
+    best
+}
@@ -0,0 +1,478 @@
+// crates/seed_architect/src/format.rs
+//
+// Conteneur .SEED chunké :
+//
+//   [SeedFileHeader (128 o)] [ChunkEntry × chunk_count] [payloads alignés sur 16 o]
+//
+// Chaque section (géométrie, matériaux, BVH...) est un chunk typé, adressé par
+// offset, avec sa propre version et son checksum. Un lecteur ignore les chunks
+// qu'il ne connaît pas : on peut ajouter des données sans casser les anciens fichiers.
+
+use bytemuck::{Pod, Zeroable};
+use serde::{Serialize, Deserialize};
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+use crate::quantize::AtomLayout;
+use dream_core::types::WorldPos;
+
+/// Signature en tête de fichier
+pub const SEED_MAGIC: [u8; 4] = *b"SEED";
+
+/// Version du conteneur.
+/// 2030 = ancien format plat (header bincode + `[f32; 6]` bruts), non supporté.
+pub const SEED_VERSION: u32 = 2031;
+
+/// Alignement des payloads (compatible `MegaBuffer::allocate(.., 16)`)
+pub const CHUNK_ALIGNMENT: u64 = 16;
+
+/// `ChunkEntry::flags` : payload compressé en zstd (`size` compressé, `raw_size` décodé)
+pub const CHUNK_FLAG_ZSTD: u32 = 1 << 0;
+
+/// `SeedFileHeader::flags` : atomes = gaussiennes 3DGS (attributs `splat_*`),
+/// à rendre triées et mélangées plutôt qu'en sprites opaques
+pub const SEED_FLAG_SPLATS: u32 = 1 << 0;
+
+/// Header fixe de 128 octets, lisible tel quel par `bytemuck`.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable, Serialize, Deserialize)]
+pub struct SeedFileHeader {
+    pub magic: [u8; 4],      // "SEED"
+    pub version: u32,        // SEED_VERSION
+    pub vertex_count: u64,   // Nombre d'atomes
+    pub index_count: u64,    // Nombre d'indices (chunk INDX), 0 si absent
+    pub bvh_offset: u64,     // Offset du chunk BVH, 0 si absent
+    pub toc_offset: u64,     // Offset de la table des chunks
+    pub chunk_count: u32,    // Nombre d'entrées dans la table
+    pub flags: u32,          // SEED_FLAG_*
+    pub atom_layout: u32,    // AtomLayout du chunk GEOM
+    pub atom_stride: u32,    // Octets par atome
+    pub content_hash: [u8; 32], // blake3(sources + réglages) : détection des bakes périmés
+    pub _reserved: [u64; 5],
+}
+
+/// Type d'un chunk (FourCC little-endian)
+#[repr(u32)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ChunkKind {
+    Geometry = u32::from_le_bytes(*b"GEOM"),
+    Materials = u32::from_le_bytes(*b"MATL"),
+    Indices = u32::from_le_bytes(*b"INDX"),
+    Bvh = u32::from_le_bytes(*b"BVH "),
+    Metadata = u32::from_le_bytes(*b"META"),
+    Attributes = u32::from_le_bytes(*b"ATTR"),
+    Ordering = u32::from_le_bytes(*b"ORDR"),
+    Objects = u32::from_le_bytes(*b"OBJT"),
+    Origin = u32::from_le_bytes(*b"ORIG"),
+}
+
+impl ChunkKind {
+    pub fn from_u32(value: u32) -> Option<Self> {
+        [
+            ChunkKind::Geometry,
+            ChunkKind::Materials,
+            ChunkKind::Indices,
+            ChunkKind::Bvh,
+            ChunkKind::Metadata,
+            ChunkKind::Attributes,
+            ChunkKind::Ordering,
+            ChunkKind::Objects,
+            ChunkKind::Origin,
+        ]
+        .into_iter()
+        .find(|k| *k as u32 == value)
+    }
+
+    /// Chunks volumineux que le baker peut compresser.
+    /// Matériaux, métadonnées et BVH restent bruts pour être lus sans décodage.
+    pub fn is_compressible(self) -> bool {
+        matches!(self, ChunkKind::Geometry | ChunkKind::Indices | ChunkKind::Attributes)
+    }
+
+    /// Version courante du payload écrit par ce baker
+    pub fn current_version(self) -> u32 {
+        match self {
+            ChunkKind::Metadata => 2, // v1 = bincode, v2 = archive rkyv
+            ChunkKind::Materials => 2, // v1 = 40 octets (emissive_ptr), v2 = 48 octets (emissive RGB)
+            _ => 1,
+        }
+    }
+}
+
+/// Entrée de la table des chunks (48 octets)
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable, Serialize, Deserialize)]
+pub struct ChunkEntry {
+    pub kind: u32,       // ChunkKind (FourCC)
+    pub version: u32,    // Version propre au payload
+    pub offset: u64,     // Position absolue dans le fichier
+    pub size: u64,       // Octets stockés
+    pub raw_size: u64,   // Octets décodés (= size pour un chunk brut)
+    pub count: u64,      // Nombre d'éléments (atomes, matériaux, noeuds...)
+    pub checksum: u32,   // CRC32 des octets stockés
+    pub flags: u32,      // CHUNK_FLAG_*
+}
+
+impl ChunkEntry {
+    pub fn kind(&self) -> Option<ChunkKind> {
+        ChunkKind::from_u32(self.kind)
+    }
+
+    pub fn is_compressed(&self) -> bool {
+        self.flags & CHUNK_FLAG_ZSTD != 0
+    }
+}
+
+/// En-tête d'un chunk ATTR : un flux d'attributs utilisateur par atome.
+/// Suivi de `count * components` valeurs `f32`.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct AttributeHeader {
+    pub name: [u8; 32],  // Nom UTF-8, complété par des zéros
+    pub components: u32, // 1 = scalaire, 3 = vec3...
+    pub _padding: u32,
+    pub count: u64,
+}
+
+impl AttributeHeader {
+    pub fn new(name: &str, components: u32, count: u64) -> Self {
+        Self { name: encode_name(name), components, _padding: 0, count }
+    }
+
+    pub fn name(&self) -> &str {
+        decode_name(&self.name)
+    }
+}
+
+/// Objet nommé (chunk OBJT) : plage contiguë d'atomes issue d'un objet/groupe OBJ (80 octets)
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct SeedObject {
+    pub name: [u8; 32],  // Nom UTF-8, complété par des zéros
+    pub first_atom: u64,
+    pub atom_count: u64,
+    pub material: u32,   // Index dans le chunk MATL
+    pub _padding: u32,
+    pub min: [f32; 3],   // AABB locale de l'objet
+    pub max: [f32; 3],
+}
+
+impl SeedObject {
+    pub fn new(name: &str, first_atom: u64, atom_count: u64, material: u32, min: [f32; 3], max: [f32; 3]) -> Self {
+        Self { name: encode_name(name), first_atom, atom_count, material, _padding: 0, min, max }
+    }
+
+    pub fn name(&self) -> &str {
+        decode_name(&self.name)
+    }
+
+    pub fn contains(&self, atom: u64) -> bool {
+        (self.first_atom..self.first_atom + self.atom_count).contains(&atom)
+    }
+}
+
+/// Nom UTF-8 tronqué à 32 octets, sans couper un caractère
+fn encode_name(name: &str) -> [u8; 32] {
+    let mut raw = [0u8; 32];
+    let mut len = name.len().min(raw.len());
+    while !name.is_char_boundary(len) {
+        len -= 1;
+    }
+    raw[..len].copy_from_slice(&name.as_bytes()[..len]);
+    raw
+}
+
+fn decode_name(raw: &[u8; 32]) -> &str {
+    let end = raw.iter().position(|&b| b == 0).unwrap_or(raw.len());
+    std::str::from_utf8(&raw[..end]).unwrap_or("")
+}
+
+/// Courbe de remplissage utilisée pour ordonner les atomes
+#[repr(u32)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SpaceCurve {
+    #[default]
+    Morton = 0,
+    Hilbert = 1,
+}
+
+impl SpaceCurve {
+    pub fn from_u32(value: u32) -> Option<Self> {
+        match value {
+            0 => Some(SpaceCurve::Morton),
+            1 => Some(SpaceCurve::Hilbert),
+            _ => None,
+        }
+    }
+}
+
+/// Chunk ORIG : origine monde en double précision (sources géoréférencées).
+/// Position réelle d'un atome = `origin` + sa position f32, relative et donc précise.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct WorldOrigin {
+    pub origin: [f64; 3],
+    pub _padding: f64,
+}
+
+impl WorldOrigin {
+    pub fn new(origin: WorldPos) -> Self {
+        Self { origin: origin.to_array(), _padding: 0.0 }
+    }
+
+    pub fn position(&self) -> WorldPos {
+        WorldPos::from_array(self.origin)
+    }
+}
+
+/// Chunk ORDR : fonction de clé ayant servi au tri des atomes.
+/// Les atomes sont triés par objet (chunk OBJT), puis par clé à l'intérieur de chaque objet.
+/// Un outil peut recalculer la clé d'un point quelconque avec `key`.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct OrderingInfo {
+    pub curve: u32,    // SpaceCurve
+    pub bits: u32,     // Bits par axe (21 -> clé sur 63 bits)
+    pub min: [f32; 3], // AABB de normalisation
+    pub max: [f32; 3],
+}
+
+impl OrderingInfo {
+    /// Bits par axe : 3 × 21 = 63 bits, la plus grande clé qui tient dans un u64
+    pub const MAX_BITS: u32 = 21;
+
+    /// Normalisation sur l'AABB des atomes, pleine résolution
+    pub fn fit(curve: SpaceCurve, atoms: &[[f32; 6]]) -> Self {
+        let mut min = [f32::MAX; 3];
+        let mut max = [f32::MIN; 3];
+        for atom in atoms {
+            for axis in 0..3 {
+                min[axis] = min[axis].min(atom[axis]);
+                max[axis] = max[axis].max(atom[axis]);
+            }
+        }
+        if atoms.is_empty() {
+            (min, max) = ([0.0; 3], [0.0; 3]);
+        }
+        Self { curve: curve as u32, bits: Self::MAX_BITS, min, max }
+    }
+
+    /// Clé de tri d'une position
+    pub fn key(&self, p: [f32; 3]) -> u64 {
+        let cells = ((1u64 << self.bits) - 1) as f32;
+        let grid = [0, 1, 2].map(|axis| {
+            let extent = self.max[axis] - self.min[axis];
+            if extent > 0.0 {
+                ((p[axis] - self.min[axis]) / extent * cells).round().clamp(0.0, cells) as u32
+            } else {
+                0
+            }
+        });
+        match SpaceCurve::from_u32(self.curve) {
+            Some(SpaceCurve::Hilbert) => crate::encode_hilbert_3d(grid[0], grid[1], grid[2], self.bits),
+            _ => crate::encode_morton_3d(grid[0], grid[1], grid[2]),
+        }
+    }
+}
+
+/// Métadonnées libres du bake (chunk META).
+/// Stockées en archive rkyv : lisibles directement depuis le mmap, sans désérialisation.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)]
+#[archive(check_bytes)]
+pub struct SeedMetadata {
+    pub generator: String,
+    pub source: String,
+}
+
+/// Écrit `<path>.tmp` dans le même dossier, le synchronise puis le renomme sur `path`.
+/// Un lecteur qui mappe l'ancien fichier garde son inode : jamais de données
+/// tronquées sous un mapping, ni de fichier à moitié écrit après un crash.
+pub fn write_atomically(path: &Path, write: impl FnOnce(&mut BufWriter<File>) -> io::Result<()>) -> io::Result<()> {
+    let mut tmp = path.as_os_str().to_owned();
+    tmp.push(".tmp");
+    let tmp = std::path::PathBuf::from(tmp);
+
+    let result = (|| {
+        let mut out = BufWriter::new(File::create(&tmp)?);
+        write(&mut out)?;
+        out.into_inner().map_err(|e| e.into_error())?.sync_all()?;
+        std::fs::rename(&tmp, path)
+    })();
+    if result.is_err() {
+        let _ = std::fs::remove_file(&tmp);
+    }
+    result
+}
+
+/// Checksum des chunks
+pub fn checksum(bytes: &[u8]) -> u32 {
+    crc32fast::hash(bytes)
+}
+
+fn align_up(value: u64, align: u64) -> u64 {
+    value.div_ceil(align) * align
+}
+
+struct PendingChunk {
+    kind: ChunkKind,
+    count: u64,
+    bytes: Vec<u8>,
+}
+
+/// Assembleur de fichiers .SEED.
+/// On empile les chunks disponibles puis `write_to` calcule les offsets.
+pub struct SeedWriter {
+    vertex_count: u64,
+    index_count: u64,
+    atom_layout: AtomLayout,
+    content_hash: [u8; 32],
+    flags: u32,
+    compression_level: Option<i32>,
+    chunks: Vec<PendingChunk>,
+}
+
+impl SeedWriter {
+    pub fn new(vertex_count: u64) -> Self {
+        Self {
+            vertex_count,
+            index_count: 0,
+            atom_layout: AtomLayout::Full,
+            content_hash: [0; 32],
+            flags: 0,
+            compression_level: None,
+            chunks: Vec::new(),
+        }
+    }
+
+    /// Encodage des atomes du chunk GEOM
+    pub fn set_atom_layout(&mut self, layout: AtomLayout) -> &mut Self {
+        self.atom_layout = layout;
+        self
+    }
+
+    /// Empreinte des entrées du bake (voir `SeedImporter::bake_hash`)
+    pub fn set_content_hash(&mut self, hash: [u8; 32]) -> &mut Self {
+        self.content_hash = hash;
+        self
+    }
+
+    /// Variante du contenu (SEED_FLAG_*)
+    pub fn set_flags(&mut self, flags: u32) -> &mut Self {
+        self.flags = flags;
+        self
+    }
+
+    /// Active la compression zstd des chunks volumineux (niveau 1..=22).
+    /// Un chunk n'est gardé compressé que s'il y gagne.
+    pub fn set_compression(&mut self, level: Option<i32>) -> &mut Self {
+        self.compression_level = level;
+        self
+    }
+
+    /// Ajoute un chunk brut
+    pub fn add_chunk(&mut self, kind: ChunkKind, count: u64, bytes: Vec<u8>) -> &mut Self {
+        if kind == ChunkKind::Indices {
+            self.index_count = count;
+        }
+        self.chunks.push(PendingChunk { kind, count, bytes });
+        self
+    }
+
+    /// Ajoute un chunk à partir d'une slice `Pod` (géométrie, matériaux...)
+    pub fn add_pod_chunk<T: Pod>(&mut self, kind: ChunkKind, data: &[T]) -> &mut Self {
+        self.add_chunk(kind, data.len() as u64, bytemuck::cast_slice(data).to_vec())
+    }
+
+    /// Ajoute un flux d'attributs par atome (chunk ATTR)
+    pub fn add_attribute(&mut self, name: &str, components: u32, data: &[f32]) -> &mut Self {
+        let count = data.len() as u64 / components.max(1) as u64;
+        let header = AttributeHeader::new(name, components, count);
+        let mut bytes = bytemuck::bytes_of(&header).to_vec();
+        bytes.extend_from_slice(bytemuck::cast_slice(data));
+        self.add_chunk(ChunkKind::Attributes, count, bytes)
+    }
+
+    /// Ajoute le chunk de métadonnées
+    pub fn add_metadata(&mut self, metadata: &SeedMetadata) -> &mut Self {
+        let bytes = rkyv::to_bytes::<_, 256>(metadata).expect("❌ Sérialisation META KO");
+        self.add_chunk(ChunkKind::Metadata, 1, bytes.into_vec())
+    }
+
+    /// Écrit le conteneur complet sur disque (remplacement atomique, voir `write_atomically`)
+    pub fn write_to(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        write_atomically(path.as_ref(), |out| self.write(out))
+    }
+
+    pub fn write<W: Write>(&self, out: &mut W) -> io::Result<()> {
+        let header_size = std::mem::size_of::<SeedFileHeader>() as u64;
+        let toc_size = (self.chunks.len() * std::mem::size_of::<ChunkEntry>()) as u64;
+
+        // 1. Compression éventuelle puis placement des payloads
+        let mut stored = Vec::with_capacity(self.chunks.len());
+        for chunk in &self.chunks {
+            let compressed = match self.compression_level {
+                Some(level) if chunk.kind.is_compressible() => {
+                    Some(zstd::stream::encode_all(chunk.bytes.as_slice(), level)?)
+                        .filter(|packed| packed.len() < chunk.bytes.len())
+                }
+                _ => None,
+            };
+            stored.push(compressed);
+        }
+
+        let mut cursor = align_up(header_size + toc_size, CHUNK_ALIGNMENT);
+        let mut entries = Vec::with_capacity(self.chunks.len());
+        for (chunk, packed) in self.chunks.iter().zip(&stored) {
+            let bytes = packed.as_deref().unwrap_or(&chunk.bytes);
+            let size = bytes.len() as u64;
+            entries.push(ChunkEntry {
+                kind: chunk.kind as u32,
+                version: chunk.kind.current_version(),
+                offset: cursor,
+                size,
+                raw_size: chunk.bytes.len() as u64,
+                count: chunk.count,
+                checksum: checksum(bytes),
+                flags: if packed.is_some() { CHUNK_FLAG_ZSTD } else { 0 },
+            });
+            cursor = align_up(cursor + size, CHUNK_ALIGNMENT);
+        }
+
+        let bvh_offset = entries.iter()
+            .find(|e| e.kind == ChunkKind::Bvh as u32)
+            .map_or(0, |e| e.offset);
+
+        let header = SeedFileHeader {
+            magic: SEED_MAGIC,
+            version: SEED_VERSION,
+            vertex_count: self.vertex_count,
+            index_count: self.index_count,
+            bvh_offset,
+            toc_offset: header_size,
+            chunk_count: entries.len() as u32,
+            flags: self.flags,
+            atom_layout: self.atom_layout as u32,
+            atom_stride: self.atom_layout.stride(),
+            content_hash: self.content_hash,
+            _reserved: [0; 5],
+        };
+
+        // 2. Écriture séquentielle (header, table, payloads + padding)
+        out.write_all(bytemuck::bytes_of(&header))?;
+        out.write_all(bytemuck::cast_slice(&entries))?;
+        let mut written = header_size + toc_size;
+
+        for ((chunk, packed), entry) in self.chunks.iter().zip(&stored).zip(&entries) {
+            let padding = (entry.offset - written) as usize;
+            out.write_all(&[0u8; CHUNK_ALIGNMENT as usize][..padding])?;
+            out.write_all(packed.as_deref().unwrap_or(&chunk.bytes))?;
+            written = entry.offset + entry.size;
+        }
+
+        let tail = (align_up(written, CHUNK_ALIGNMENT) - written) as usize;
+        out.write_all(&[0u8; CHUNK_ALIGNMENT as usize][..tail])?;
+        Ok(())
+    }
+}
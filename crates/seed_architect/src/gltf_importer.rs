@@ -0,0 +1,193 @@
+// crates/seed_architect/src/gltf_importer.rs
+//
+// Ingestion glTF 2.0 / GLB. Produit la même scène brute que l'OBJ
+// (`RawScene`), puis passe par le bake commun de `SeedImporter` :
+//   - hiérarchie de nœuds aplatie (positions et normales en espace monde)
+//   - une primitive = un objet (nom du nœud), avec son matériau
+//   - matériaux metallic-roughness, textures embarquées ou externes
+//   - couleurs de sommet (COLOR_0) multipliées dans l'attribut "color"
+// Couleurs : baseColorTexture est la seule donnée sRGB (décodée une fois ici) ;
+// baseColorFactor et COLOR_0 sont linéaires selon la spec et passent tels quels.
+
+use crate::importer::{BakeSettings, MaterialData, MeshData, RawScene, SeedImporter};
+use crate::texture::{srgb_to_linear, MaterialTextures, Texture};
+use glam::{Mat4, Vec2, Vec3};
+use gltf::image::Format;
+use gltf::mesh::Mode;
+use log::warn;
+use std::path::{Path, PathBuf};
+
+pub struct GltfImporter;
+
+impl GltfImporter {
+    pub fn import_and_bake(path: &str, output_path: &str) {
+        Self::import_and_bake_with(path, output_path, &BakeSettings::default());
+    }
+
+    pub fn import_and_bake_with(path: &str, output_path: &str, settings: &BakeSettings) {
+        let raw = Self::load_raw_gltf(path, settings.crease_angle);
+        SeedImporter::bake(&raw, path, output_path, settings);
+    }
+
+    /// Extensions prises en charge : .gltf (JSON + fichiers) et .glb (binaire)
+    pub fn handles(path: &str) -> bool {
+        Path::new(path).extension()
+            .and_then(|e| e.to_str())
+            .is_some_and(|e| e.eq_ignore_ascii_case("gltf") || e.eq_ignore_ascii_case("glb"))
+    }
+
+    /// Buffers et images externes (les URI `data:` et le chunk binaire GLB sont dans le fichier)
+    pub fn dependencies(path: &str) -> Vec<PathBuf> {
+        let dir = Path::new(path).parent().unwrap_or(Path::new(""));
+        let Ok(gltf) = gltf::Gltf::open(path) else {
+            return Vec::new();
+        };
+        let buffers = gltf.buffers().filter_map(|b| match b.source() {
+            gltf::buffer::Source::Uri(uri) => Some(uri),
+            gltf::buffer::Source::Bin => None,
+        });
+        let images = gltf.images().filter_map(|i| match i.source() {
+            gltf::image::Source::Uri { uri, .. } => Some(uri),
+            gltf::image::Source::View { .. } => None,
+        });
+        buffers.chain(images)
+            .filter(|uri| !uri.starts_with("data:"))
+            .map(|uri| dir.join(uri))
+            .collect()
+    }
+
+    /// Scène par défaut (ou la première) aplatie en soupe de triangles monde
+    pub(crate) fn load_raw_gltf(path: &str, crease_angle: f32) -> RawScene {
+        let (document, buffers, images) = gltf::import(path)
+            .unwrap_or_else(|e| panic!("❌ Erreur Ingestion glTF {} : {}", path, e));
+
+        let mut raw = RawScene::default();
+        for material in document.materials() {
+            raw.materials.push(MaterialData::from_gltf(&material));
+            raw.textures.push(material_textures(&material, &images));
+        }
+        // Matériau glTF par défaut, ajouté seulement si une primitive n'en a pas
+        let mut default_material = None;
+
+        let Some(scene) = document.default_scene().or_else(|| document.scenes().next()) else {
+            warn!("⚠️ {} ne contient aucune scène", path);
+            return raw;
+        };
+        let mut stack: Vec<(gltf::Node, Mat4)> = scene.nodes().map(|n| (n, Mat4::IDENTITY)).collect();
+        while let Some((node, parent)) = stack.pop() {
+            let world = parent * Mat4::from_cols_array_2d(&node.transform().matrix());
+            stack.extend(node.children().map(|child| (child, world)));
+            let Some(mesh) = node.mesh() else { continue };
+
+            let name = node.name().or(mesh.name()).map_or_else(|| format!("node_{}", node.index()), str::to_string);
+            for primitive in mesh.primitives() {
+                let material = match primitive.material().index() {
+                    Some(id) => id as u32,
+                    None => *default_material.get_or_insert_with(|| {
+                        raw.materials.push(MaterialData::from_gltf(&primitive.material()));
+                        raw.textures.push(MaterialTextures::default());
+                        raw.materials.len() as u32 - 1
+                    }),
+                };
+                if let Some(mesh) = Self::read_primitive(&primitive, &buffers) {
+                    raw.add_mesh(name.clone(), material, mesh, world, crease_angle);
+                }
+            }
+        }
+        raw
+    }
+
+    /// Primitive en liste de triangles (espace local) : positions, normales, UV, couleurs
+    fn read_primitive(primitive: &gltf::Primitive, buffers: &[gltf::buffer::Data]) -> Option<MeshData> {
+        let reader = primitive.reader(|b| buffers.get(b.index()).map(|data| &data.0[..]));
+        let Some(positions) = reader.read_positions() else {
+            warn!("⚠️ Primitive glTF sans positions ignorée");
+            return None;
+        };
+        let positions: Vec<Vec3> = positions.map(Vec3::from).collect();
+        let order: Vec<u32> = match reader.read_indices() {
+            Some(indices) => indices.into_u32().collect(),
+            None => (0..positions.len() as u32).collect(),
+        };
+        let indices: Vec<u32> = match primitive.mode() {
+            Mode::Triangles => order.chunks_exact(3).flatten().copied().collect(),
+            Mode::TriangleStrip => order.windows(3).enumerate()
+                .flat_map(|(i, w)| if i % 2 == 0 { [w[0], w[1], w[2]] } else { [w[1], w[0], w[2]] })
+                .collect(),
+            Mode::TriangleFan => order.windows(2).skip(1)
+                .flat_map(|w| [order[0], w[0], w[1]])
+                .collect(),
+            mode => {
+                warn!("⚠️ Primitive glTF {:?} ignorée (triangles uniquement)", mode);
+                return None;
+            }
+        };
+
+        // UV du jeu utilisé par la texture de couleur (V inversé : convention OBJ, V vers le haut)
+        let pbr = primitive.material().pbr_metallic_roughness();
+        let set = pbr.base_color_texture().or(pbr.metallic_roughness_texture()).map_or(0, |t| t.tex_coord());
+        Some(MeshData {
+            positions,
+            normals: reader.read_normals().map(|n| n.map(Vec3::from).collect()),
+            uvs: reader.read_tex_coords(set).map(|uv| uv.into_f32().map(|[u, v]| Vec2::new(u, 1.0 - v)).collect()),
+            // Linéaires (spec glTF), comme l'attribut "color"
+            colors: reader.read_colors(0).map(|c| c.into_rgb_f32().map(Vec3::from).collect()),
+            indices,
+        })
+    }
+}
+
+/// Textures d'un matériau glTF, facteurs pré-multipliés.
+/// baseColor est sRGB (spec glTF) : linéarisée avant le facteur, linéaire.
+/// metallicRoughness est linéaire et éclatée : rugosité = canal G, métal = canal B.
+fn material_textures(material: &gltf::Material, images: &[gltf::image::Data]) -> MaterialTextures {
+    let pbr = material.pbr_metallic_roughness();
+    let image = |info: Option<gltf::texture::Info>| {
+        info.and_then(|info| images.get(info.texture().source().index()))
+    };
+    let [r, g, b, _] = pbr.base_color_factor();
+    let (roughness, metallic) = (pbr.roughness_factor(), pbr.metallic_factor());
+
+    let metallic_roughness = image(pbr.metallic_roughness_texture());
+    MaterialTextures {
+        diffuse: image(pbr.base_color_texture()).map(|data| {
+            decode(data, |t| [srgb_to_linear(t[0]) * r, srgb_to_linear(t[1]) * g, srgb_to_linear(t[2]) * b, t[3]])
+        }),
+        roughness: metallic_roughness.map(|data| decode(data, |t| [t[1] * roughness; 4])),
+        metallic: metallic_roughness.map(|data| decode(data, |t| [t[2] * metallic; 4])),
+    }
+}
+
+/// Image glTF décodée -> texture RGBA flottante (gris et gris+alpha répliqués sur RGB)
+fn decode(data: &gltf::image::Data, remap: impl Fn([f32; 4]) -> [f32; 4]) -> Texture {
+    let (channels, size) = match data.format {
+        Format::R8 => (1, 1),
+        Format::R8G8 => (2, 1),
+        Format::R8G8B8 => (3, 1),
+        Format::R8G8B8A8 => (4, 1),
+        Format::R16 => (1, 2),
+        Format::R16G16 => (2, 2),
+        Format::R16G16B16 => (3, 2),
+        Format::R16G16B16A16 => (4, 2),
+        Format::R32G32B32FLOAT => (3, 4),
+        Format::R32G32B32A32FLOAT => (4, 4),
+    };
+    let component = |bytes: &[u8]| match size {
+        1 => bytes[0] as f32 / 255.0,
+        2 => u16::from_le_bytes([bytes[0], bytes[1]]) as f32 / 65535.0,
+        _ => f32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]),
+    };
+    let texels = data.pixels.chunks_exact(channels * size).map(|px| {
+        let mut c = [0.0; 4];
+        for (k, bytes) in px.chunks_exact(size).enumerate() {
+            c[k] = component(bytes);
+        }
+        remap(match channels {
+            1 => [c[0], c[0], c[0], 1.0],
+            2 => [c[0], c[0], c[0], c[1]],
+            3 => [c[0], c[1], c[2], 1.0],
+            _ => [c[0], c[1], c[2], c[3]],
+        })
+    }).collect();
+    Texture::new(data.width, data.height, texels)
+}
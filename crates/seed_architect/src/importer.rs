@@ -1,81 +1,581 @@
 use bytemuck::{Pod, Zeroable};
-use std::fs::File;
-use std::io::Write;
-use crate::{SeedFileHeader, encode_morton_3d};
-use rand::Rng;
+use crate::bvh::{build_lbvh, lbvh_depth, GPU_STACK_SIZE};
+use crate::format::{ChunkKind, OrderingInfo, SeedMetadata, SeedObject, SeedWriter, SpaceCurve, WorldOrigin, SEED_FLAG_SPLATS};
+use crate::quantize::{dequantize_atoms, quantize_atoms, AtomLayout};
+use crate::sampling::{sample_surface, SamplingMode};
+use crate::voxelize::fill_interior;
+use crate::normals::{point_normals, vertex_normals};
+use crate::texture::{barycentric, interpolate_uv, srgb_to_linear, texture_path, MaterialTextures, Texture, TEXTURE_KEYS};
+use crate::gltf_importer::GltfImporter;
+use crate::assimp_importer::AssimpImporter;
+use crate::ply::PlyImporter;
+use crate::lidar::LasImporter;
+use dream_core::types::WorldPos;
+use russimp::material::PropertyTypeInfo;
+use russimp::texture::TextureType;
+use glam::{Mat3, Mat4, Vec2, Vec3};
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use log::warn;
+use std::path::{Path, PathBuf};
 
+/// Matériau PBR (48 octets, tableau std430 côté GLSL)
 #[repr(C)]
 #[derive(Clone, Copy, Debug, Pod, Zeroable, serde::Serialize, serde::Deserialize)]
 pub struct MaterialData {
     pub base_color: [f32; 3],
     pub metallic: f32,
-    pub emissive_ptr: u64,
+    pub emissive: [f32; 3],
     pub roughness: f32,
     pub ior: f32,
-    pub _padding: [u32; 2],
+    pub _padding: [u32; 3],
+}
+
+impl MaterialData {
+    /// Placeholder quand l'OBJ n'a pas de MTL (Gold, sRGB (1, 0.84, 0) linéarisé)
+    pub const GOLD: MaterialData = MaterialData {
+        base_color: [1.0, 0.674, 0.0],
+        metallic: 1.0,
+        emissive: [0.0; 3],
+        roughness: 0.3,
+        ior: 1.45,
+        _padding: [0; 3],
+    };
+
+    /// Conversion MTL -> PBR :
+    ///   - Kd (sRGB) -> base_color linéaire, Ke -> emissive, Ni -> ior
+    ///   - Pr / Pm (extension PBR) si présents
+    ///   - sinon rugosité déduite de Ns (Blinn-Phong -> GGX), 1.0 si Ks est noir
+    pub fn from_mtl(m: &tobj::Material) -> Self {
+        let scalar = |key: &str| m.unknown_param.get(key).and_then(|v| v.trim().parse::<f32>().ok());
+        let roughness = scalar("Pr").unwrap_or_else(|| {
+            roughness_from_shininess(m.specular.unwrap_or([0.0; 3]), m.shininess.unwrap_or(0.0))
+        });
+
+        Self {
+            base_color: m.diffuse.unwrap_or([0.8; 3]).map(srgb_to_linear),
+            metallic: scalar("Pm").unwrap_or(0.0).clamp(0.0, 1.0),
+            emissive: m.emissive.unwrap_or([0.0; 3]),
+            roughness: roughness.clamp(0.0, 1.0),
+            ior: m.optical_density.unwrap_or(1.45),
+            _padding: [0; 3],
+        }
+    }
+
+    /// Conversion glTF metallic-roughness (facteurs seuls, les textures sont
+    /// échantillonnées par atome). baseColorFactor est déjà linéaire.
+    /// Émission × KHR_materials_emissive_strength.
+    pub fn from_gltf(m: &gltf::Material) -> Self {
+        let pbr = m.pbr_metallic_roughness();
+        let [r, g, b, _] = pbr.base_color_factor();
+        let strength = m.emissive_strength().unwrap_or(1.0);
+        Self {
+            base_color: [r, g, b],
+            metallic: pbr.metallic_factor().clamp(0.0, 1.0),
+            emissive: m.emissive_factor().map(|c| c * strength),
+            roughness: pbr.roughness_factor().clamp(0.0, 1.0),
+            ior: m.ior().unwrap_or(1.5),
+            _padding: [0; 3],
+        }
+    }
+
+    /// Conversion des propriétés Assimp (`$clr.base`, `$mat.metallicFactor`...).
+    /// Sans facteurs PBR (FBX, Collada classiques), même déduction Blinn-Phong que le MTL.
+    /// `$clr.base` est linéaire (facteur PBR) ; `$clr.diffuse`, sRGB comme un Kd, est linéarisé.
+    pub fn from_assimp(m: &russimp::material::Material) -> Self {
+        let floats = |key: &str| m.properties.iter()
+            .find(|p| p.key == key && p.semantic == TextureType::None)
+            .and_then(|p| match &p.data {
+                PropertyTypeInfo::FloatArray(values) => Some(values.as_slice()),
+                _ => None,
+            });
+        let color = |key: &str| floats(key).filter(|v| v.len() >= 3).map(|v| [v[0], v[1], v[2]]);
+        let scalar = |key: &str| floats(key).and_then(|v| v.first().copied());
+        let roughness = scalar("$mat.roughnessFactor").unwrap_or_else(|| {
+            roughness_from_shininess(color("$clr.specular").unwrap_or([0.0; 3]), scalar("$mat.shininess").unwrap_or(0.0))
+        });
+
+        Self {
+            base_color: color("$clr.base")
+                .unwrap_or_else(|| color("$clr.diffuse").unwrap_or([0.8; 3]).map(srgb_to_linear)),
+            metallic: scalar("$mat.metallicFactor").unwrap_or(0.0).clamp(0.0, 1.0),
+            emissive: color("$clr.emissive").unwrap_or([0.0; 3]),
+            roughness: roughness.clamp(0.0, 1.0),
+            ior: scalar("$mat.refracti").unwrap_or(1.45),
+            _padding: [0; 3],
+        }
+    }
+}
+
+/// Rugosité GGX équivalente à un exposant Blinn-Phong (1.0 si la spéculaire est noire)
+fn roughness_from_shininess(specular: [f32; 3], shininess: f32) -> f32 {
+    if specular.iter().all(|&c| c <= 0.0) {
+        1.0
+    } else {
+        // alpha = sqrt(2 / (Ns + 2)), rugosité perceptuelle = sqrt(alpha)
+        (2.0 / (shininess.max(0.0) + 2.0)).powf(0.25)
+    }
+}
+
+/// Réglages du bake.
+/// Deux bakes des mêmes sources avec les mêmes réglages sont identiques au bit près.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct BakeSettings {
+    /// Graine du générateur (jitter, échantillonnage)
+    pub seed: u64,
+    /// Niveau zstd des chunks volumineux (None = stockage brut)
+    pub compression_level: Option<i32>,
+    /// Encodage des atomes (Full = 24 octets, Quantized = 12 octets)
+    pub atom_layout: AtomLayout,
+    /// Un atome par sommet, ou densité cible sur la surface
+    pub sampling: SamplingMode,
+    /// Courbe du tri spatial des atomes
+    pub ordering: SpaceCurve,
+    /// Remplissage volumique (voxels sur le plus grand axe), None = surface seule.
+    /// Le maillage doit être fermé.
+    pub voxel_fill: Option<u32>,
+    /// Angle de pli (degrés) des normales calculées quand la source n'en a pas
+    pub crease_angle: f32,
+}
+
+impl Default for BakeSettings {
+    fn default() -> Self {
+        Self {
+            seed: 0,
+            compression_level: None,
+            atom_layout: AtomLayout::default(),
+            sampling: SamplingMode::default(),
+            ordering: SpaceCurve::default(),
+            voxel_fill: None,
+            crease_angle: 60.0,
+        }
+    }
+}
+
+/// Attribut ATTR marquant les atomes intérieurs (0 = surface, 1 = intérieur)
+pub const INTERIOR_ATTRIBUTE: &str = "interior";
+
+/// Attribut ATTR : index du matériau de chaque atome (absent = matériau 0)
+pub const MATERIAL_ATTRIBUTE: &str = "material";
+
+/// Attributs ATTR échantillonnés dans les textures (map_Kd, map_Pr, map_Pm, ou
+/// baseColor / metallicRoughness en glTF). La couleur est multipliée par la
+/// couleur de sommet quand la source en a.
+/// Les atomes d'un matériau sans texture reçoivent la constante du matériau.
+pub const COLOR_ATTRIBUTE: &str = "color";
+pub const ROUGHNESS_ATTRIBUTE: &str = "roughness";
+pub const METALLIC_ATTRIBUTE: &str = "metallic";
+
+/// Scène chargée (OBJ, glTF) : soupe de triangles + informations par triangle.
+/// Tout le bake part de cette représentation, quel que soit le format source.
+#[derive(Default)]
+pub(crate) struct RawScene {
+    /// Source sans faces : sommets seuls, normales estimées par PCA
+    pub points: Vec<[f32; 6]>,
+    /// Position + normale, 3 sommets consécutifs par triangle
+    pub geometry: Vec<f32>,
+    /// Index du matériau de chaque triangle
+    pub triangle_materials: Vec<u32>,
+    /// Index de l'objet de chaque triangle
+    pub triangle_objects: Vec<u32>,
+    /// Nom et matériau de chaque objet
+    pub objects: Vec<(String, u32)>,
+    /// UV des 3 coins de chaque triangle (None si le maillage n'en a pas, V vers le haut)
+    pub triangle_uvs: Vec<Option<[Vec2; 3]>>,
+    /// Couleur de sommet des 3 coins de chaque triangle (None si absente)
+    pub triangle_colors: Vec<Option<[Vec3; 3]>>,
+    pub materials: Vec<MaterialData>,
+    pub textures: Vec<MaterialTextures>,
+    /// Attributs par point des nuages (nom, composantes, valeurs), écrits tels quels
+    pub point_attributes: Vec<(String, u32, Vec<f32>)>,
+    /// Origine double précision des sources géoréférencées (positions relatives à elle)
+    pub origin: Option<WorldPos>,
+    /// Gaussiennes 3DGS : header marqué SEED_FLAG_SPLATS
+    pub splats: bool,
+}
+
+/// Maillage triangulé d'une source, en espace local
+pub(crate) struct MeshData {
+    pub positions: Vec<Vec3>,
+    /// Normales par sommet (None = lissage aire × angle, un seul groupe)
+    pub normals: Option<Vec<Vec3>>,
+    /// UV par sommet, V vers le haut
+    pub uvs: Option<Vec<Vec2>>,
+    pub colors: Option<Vec<Vec3>>,
+    /// 3 index consécutifs par triangle
+    pub indices: Vec<u32>,
+}
+
+impl RawScene {
+    /// Ajoute `mesh`, placé par `world`, comme objet `name` de matériau `material`.
+    /// Une transformation miroir inverse l'enroulement pour garder les normales sortantes.
+    pub fn add_mesh(&mut self, name: String, material: u32, mesh: MeshData, world: Mat4, crease_angle: f32) {
+        let mirrored = world.determinant() < 0.0;
+        let vertex_count = mesh.positions.len();
+        // Triangles référençant un sommet absent écartés
+        let indices: Vec<usize> = mesh.indices.chunks_exact(3)
+            .filter(|t| t.iter().all(|&i| (i as usize) < vertex_count))
+            .flat_map(|t| if mirrored { [t[0], t[2], t[1]] } else { [t[0], t[1], t[2]] })
+            .map(|i| i as usize)
+            .collect();
+
+        let corners: Vec<Vec3> = indices.iter().map(|&i| world.transform_point3(mesh.positions[i])).collect();
+        let normals: Vec<Vec3> = match &mesh.normals {
+            Some(normals) => {
+                let normal_matrix = Mat3::from_mat4(world).inverse().transpose();
+                indices.iter()
+                    .map(|&i| normals.get(i).map_or(Vec3::ZERO, |n| (normal_matrix * *n).normalize_or_zero()))
+                    .collect()
+            }
+            None => vertex_normals(&corners, &vec![1; corners.len() / 3], crease_angle),
+        };
+        for (p, n) in corners.iter().zip(&normals) {
+            let n = if *n == Vec3::ZERO { Vec3::Y } else { *n };
+            self.geometry.extend_from_slice(&[p.x, p.y, p.z, n.x, n.y, n.z]);
+        }
+
+        // Valeurs des 3 coins, si le canal existe pour chacun d'eux
+        fn corners_of<T: Copy>(values: &Option<Vec<T>>, tri: &[usize]) -> Option<[T; 3]> {
+            values.as_ref()
+                .filter(|v| tri.iter().all(|&i| i < v.len()))
+                .map(|v| [v[tri[0]], v[tri[1]], v[tri[2]]])
+        }
+        for tri in indices.chunks_exact(3) {
+            self.triangle_uvs.push(corners_of(&mesh.uvs, tri));
+            self.triangle_colors.push(corners_of(&mesh.colors, tri));
+        }
+
+        let tri_count = indices.len() / 3;
+        self.triangle_materials.extend(std::iter::repeat_n(material, tri_count));
+        self.triangle_objects.extend(std::iter::repeat_n(self.objects.len() as u32, tri_count));
+        self.objects.push((name, material));
+    }
+}
+
+/// Informations relues directement dans le fichier OBJ
+#[derive(Default)]
+struct ObjScan {
+    smoothing: Vec<u32>,
+    points: Vec<Vec3>,
+}
+
+/// Valeurs échantillonnées par atome (présentes seulement si une texture les fournit)
+#[derive(Default)]
+struct AtomTexels {
+    color: Option<Vec<[f32; 3]>>,
+    roughness: Option<Vec<f32>>,
+    metallic: Option<Vec<f32>>,
 }
 
 pub struct SeedImporter;
 
 impl SeedImporter {
     pub fn import_and_bake(path: &str, output_path: &str) {
-        let (geometry, _materials) = Self::load_raw_obj(path);
+        Self::import_and_bake_with(path, output_path, &BakeSettings::default());
+    }
+
+    /// Format choisi par l'extension : glTF/GLB, PLY, LAS/LAZ, formats Assimp (FBX, DAE, 3DS...), OBJ sinon
+    pub fn import_and_bake_with(path: &str, output_path: &str, settings: &BakeSettings) {
+        let raw = if GltfImporter::handles(path) {
+            GltfImporter::load_raw_gltf(path, settings.crease_angle)
+        } else if PlyImporter::handles(path) {
+            PlyImporter::load_raw_ply(path, settings.crease_angle)
+        } else if LasImporter::handles(path) {
+            LasImporter::load_raw_las(path)
+        } else if AssimpImporter::handles(path) {
+            AssimpImporter::load_raw_assimp(path, settings.crease_angle)
+        } else {
+            Self::load_raw_obj(path, settings.crease_angle)
+        };
+        Self::bake(&raw, path, output_path, settings);
+    }
+
+    /// Bake commun à tous les formats : atomes, tri, LBVH, objets, attributs
+    pub(crate) fn bake(raw: &RawScene, path: &str, output_path: &str, settings: &BakeSettings) {
+        let RawScene { geometry, triangle_materials, triangle_objects, materials, .. } = raw;
         
-        let mut rng = rand::thread_rng();
+        // ChaCha8 : séquence fixée par l'algorithme, stable d'une version de rand à l'autre
+        let mut rng = ChaCha8Rng::seed_from_u64(settings.seed);
         
         // CORRECTION ICI :
         // 0.5 était trop grand. On passe à 0.02 pour juste "épaissir" la surface
         // sans détruire les traits du visage de Suzanne.
         let dispersion = 0.02; 
 
-        // 1. Conversion et Injection du Bruit
-        let mut vertices: Vec<[f32; 6]> = Vec::with_capacity(geometry.len() / 6);
-        for i in (0..geometry.len()).step_by(6) {
-            if i + 5 < geometry.len() {
-                // Jitter très faible pour garder la forme
-                let jitter_x = rng.gen_range(-dispersion..dispersion);
-                let jitter_y = rng.gen_range(-dispersion..dispersion);
-                let jitter_z = rng.gen_range(-dispersion..dispersion);
-
-                vertices.push([
-                    geometry[i]   + jitter_x, 
-                    geometry[i+1] + jitter_y, 
-                    geometry[i+2] + jitter_z, 
-                    geometry[i+3], geometry[i+4], geometry[i+5]
-                ]);
+        // 1. Génération des atomes (sommets ou échantillonnage de surface)
+        let triangulated: Vec<[f32; 6]> = geometry.chunks_exact(6)
+            .map(|v| [v[0], v[1], v[2], v[3], v[4], v[5]])
+            .collect();
+        let (mut vertices, mut sources) = if triangulated.is_empty() {
+            // Nuage de points : un atome par point, aucun triangle source
+            (raw.points.clone(), vec![0; raw.points.len()])
+        } else {
+            sample_surface(&triangulated, settings.sampling, &mut rng)
+        };
+        let surface_count = vertices.len();
+
+        // Remplissage volumique : atomes intérieurs ajoutés après la surface
+        if let Some(resolution) = settings.voxel_fill {
+            let (interior, interior_sources) = fill_interior(&triangulated, resolution);
+            vertices.extend(interior);
+            sources.extend(interior_sources);
+        }
+
+        // Textures lues aux UV des atomes (avant le jitter : UV du point échantillonné)
+        let texels = Self::sample_textures(raw, &triangulated, &vertices, &sources);
+
+        // Injection du Bruit (jitter très faible pour garder la forme).
+        // Jamais sur un nuage de points : ce sont des mesures.
+        if !triangulated.is_empty() {
+            for v in &mut vertices {
+                v[0] += rng.random_range(-dispersion..dispersion);
+                v[1] += rng.random_range(-dispersion..dispersion);
+                v[2] += rng.random_range(-dispersion..dispersion);
             }
-        }   
-
-        // 2. Tri Morton (Optimisation Cache GPU)
-        vertices.sort_by_key(|v| {
-            let x = ((v[0] + 512.0) * 100.0) as u32;
-            let y = ((v[1] + 512.0) * 100.0) as u32;
-            let z = ((v[2] + 512.0) * 100.0) as u32;
-            encode_morton_3d(x, y, z)
-        });
+        }
 
-        // 3. Écriture du Fichier .SEED
-        let mut file = File::create(output_path).expect("❌ Impossible de créer le fichier .seed");
-        
-        // Header
-        let header = SeedFileHeader {
-            magic: *b"SEED",
-            version: 2030,
-            vertex_count: vertices.len() as u64,
-            index_count: 0,
-            bvh_offset: 0, 
-            material_ptr: 0, 
+        // 2. Tri par objet puis spatial (Optimisation Cache GPU)
+        // Chaque objet devient une plage contiguë d'atomes.
+        // Coordonnées normalisées sur l'AABB -> pleine précision sur 21 bits par axe.
+        // On trie une permutation pour que les attributs par atome suivent le même ordre
+        let atom_objects: Vec<u32> = if triangulated.is_empty() {
+            vec![0; vertices.len()] // Nuage de points : un seul objet
+        } else {
+            sources.iter().map(|&tri| triangle_objects[tri as usize]).collect()
+        };
+        let ordering = OrderingInfo::fit(settings.ordering, &vertices);
+        let sort_key = |i: usize| {
+            let key = ordering.key([vertices[i][0], vertices[i][1], vertices[i][2]]);
+            ((atom_objects[i] as u128) << 64) | key as u128
+        };
+        let mut order: Vec<usize> = (0..vertices.len()).collect();
+        order.sort_by_cached_key(|&i| sort_key(i));
+        let keys: Vec<u128> = order.iter().map(|&i| sort_key(i)).collect();
+        let vertices: Vec<[f32; 6]> = order.iter().map(|&i| vertices[i]).collect();
+
+        // 3. Écriture du Fichier .SEED (un chunk par section disponible)
+        let (geometry_bytes, decoded) = match settings.atom_layout {
+            AtomLayout::Full => (bytemuck::cast_slice(&vertices).to_vec(), None),
+            AtomLayout::Quantized => {
+                let (bounds, atoms) = quantize_atoms(&vertices);
+                let mut bytes = bytemuck::bytes_of(&bounds).to_vec();
+                bytes.extend_from_slice(bytemuck::cast_slice(&atoms));
+                (bytes, Some(dequantize_atoms(&bounds, &atoms)))
+            }
+        };
+
+        // 4. LBVH et objets sur les positions telles que le runtime les lira
+        let positions = decoded.as_deref().unwrap_or(&vertices);
+        let mut bvh = build_lbvh(positions, &keys);
+        let depth = lbvh_depth(&bvh);
+        if depth + 1 > GPU_STACK_SIZE {
+            // Le picker abandonnerait des branches : pas de chunk BVH, force brute au runtime
+            warn!("⚠️ LBVH de profondeur {} (pile GPU : {}) : chunk BVH omis", depth, GPU_STACK_SIZE);
+            bvh.clear();
+        }
+        let objects = Self::object_ranges(&raw.objects, positions, order.iter().map(|&i| atom_objects[i]));
+
+        let mut writer = SeedWriter::new(vertices.len() as u64);
+        writer
+            .set_compression(settings.compression_level)
+            .set_atom_layout(settings.atom_layout)
+            .set_flags(if raw.splats { SEED_FLAG_SPLATS } else { 0 })
+            .set_content_hash(Self::bake_hash(path, settings))
+            .add_chunk(ChunkKind::Geometry, vertices.len() as u64, geometry_bytes) // Positions + Normales
+            .add_pod_chunk(ChunkKind::Ordering, &[ordering])
+            .add_pod_chunk(ChunkKind::Objects, &objects)
+            .add_metadata(&SeedMetadata {
+                generator: format!("seed_architect {}", env!("CARGO_PKG_VERSION")),
+                source: path.to_string(),
+            });
+        if !bvh.is_empty() {
+            writer.add_pod_chunk(ChunkKind::Bvh, &bvh);
+        }
+        if !materials.is_empty() {
+            writer.add_pod_chunk(ChunkKind::Materials, materials);
+        }
+        if let Some(origin) = raw.origin {
+            writer.add_pod_chunk(ChunkKind::Origin, &[WorldOrigin::new(origin)]);
+        }
+        if materials.len() > 1 && !triangulated.is_empty() {
+            let indices: Vec<f32> = order.iter()
+                .map(|&i| triangle_materials[sources[i] as usize] as f32)
+                .collect();
+            writer.add_attribute(MATERIAL_ATTRIBUTE, 1, &indices);
+        }
+        if let Some(color) = &texels.color {
+            let data: Vec<f32> = order.iter().flat_map(|&i| color[i]).collect();
+            writer.add_attribute(COLOR_ATTRIBUTE, 3, &data);
+        }
+        for (name, values) in [(ROUGHNESS_ATTRIBUTE, &texels.roughness), (METALLIC_ATTRIBUTE, &texels.metallic)] {
+            if let Some(values) = values {
+                let data: Vec<f32> = order.iter().map(|&i| values[i]).collect();
+                writer.add_attribute(name, 1, &data);
+            }
+        }
+        if triangulated.is_empty() {
+            // Nuage de points : attributs de la source, un par point, dans l'ordre du tri
+            for (name, components, values) in &raw.point_attributes {
+                let n = *components as usize;
+                let data: Vec<f32> = order.iter().flat_map(|&i| &values[i * n..(i + 1) * n]).copied().collect();
+                writer.add_attribute(name, *components, &data);
+            }
+        }
+        if settings.voxel_fill.is_some() {
+            // 0 = atome de surface, 1 = atome intérieur
+            let interior: Vec<f32> = order.iter()
+                .map(|&i| if i >= surface_count { 1.0 } else { 0.0 })
+                .collect();
+            writer.add_attribute(INTERIOR_ATTRIBUTE, 1, &interior);
+        }
+
+        writer.write_to(output_path).expect("❌ Impossible d'écrire le fichier .seed");
+    }
+
+    /// Source qu'un des importeurs sait lire (OBJ compris)
+    pub fn handles(path: &str) -> bool {
+        let is_obj = Path::new(path).extension()
+            .and_then(|e| e.to_str())
+            .is_some_and(|e| e.eq_ignore_ascii_case("obj"));
+        is_obj
+            || GltfImporter::handles(path)
+            || PlyImporter::handles(path)
+            || LasImporter::handles(path)
+            || AssimpImporter::handles(path)
+    }
+
+    /// Fichiers dont dépend une source, selon son extension
+    pub fn dependencies(path: &str) -> Vec<PathBuf> {
+        if GltfImporter::handles(path) {
+            GltfImporter::dependencies(path)
+        } else if PlyImporter::handles(path) || LasImporter::handles(path) {
+            Vec::new() // Autonomes
+        } else if AssimpImporter::handles(path) {
+            AssimpImporter::dependencies(path)
+        } else {
+            Self::obj_dependencies(path)
+        }
+    }
+
+    /// Fichiers dont dépend un OBJ (bibliothèques `mtllib` et leurs textures), chemins résolus
+    pub fn obj_dependencies(path: &str) -> Vec<PathBuf> {
+        let dir = Path::new(path).parent().unwrap_or(Path::new(""));
+        let Ok(source) = std::fs::read_to_string(path) else {
+            return Vec::new();
+        };
+        let libraries: Vec<PathBuf> = source.lines()
+            .filter_map(|line| line.trim().strip_prefix("mtllib"))
+            .flat_map(|libs| libs.split_whitespace())
+            .map(|lib| dir.join(lib))
+            .collect();
+
+        let mut dependencies = libraries.clone();
+        for lib in &libraries {
+            let Ok(mtl) = std::fs::read_to_string(lib) else { continue };
+            let lib_dir = lib.parent().unwrap_or(Path::new(""));
+            dependencies.extend(mtl.lines().filter_map(|line| {
+                let (key, value) = line.trim().split_once(char::is_whitespace)?;
+                TEXTURE_KEYS.contains(&key).then(|| texture_path(value, lib_dir)).flatten()
+            }));
+        }
+        dependencies
+    }
+
+    /// Empreinte blake3 d'un bake : version du baker, contenu de la source et de ses
+    /// dépendances, réglages. Si elle diffère de `SeedReader::content_hash`,
+    /// le .seed est périmé.
+    pub fn bake_hash(path: &str, settings: &BakeSettings) -> [u8; 32] {
+        let mut hasher = blake3::Hasher::new();
+        hasher.update(&crate::format::SEED_VERSION.to_le_bytes());
+        hasher.update(env!("CARGO_PKG_VERSION").as_bytes());
+
+        let sources = std::iter::once(PathBuf::from(path)).chain(Self::dependencies(path));
+        for source in sources {
+            // Une dépendance absente compte aussi : son apparition invalide le bake
+            let bytes = std::fs::read(&source).unwrap_or_default();
+            hasher.update(&(bytes.len() as u64).to_le_bytes());
+            hasher.update(&bytes);
+        }
+
+        // Debug couvre automatiquement tout nouveau réglage (f32 imprimés sans perte)
+        hasher.update(format!("{:?}", settings).as_bytes());
+        *hasher.finalize().as_bytes()
+    }
+
+    /// Plages contiguës d'atomes par objet (atomes déjà triés par objet) et leur AABB.
+    /// Les objets sans atome sont omis.
+    fn object_ranges(objects: &[(String, u32)], atoms: &[[f32; 6]], atom_objects: impl Iterator<Item = u32>) -> Vec<SeedObject> {
+        let mut ranges: Vec<SeedObject> = Vec::new();
+        let mut current = None;
+        for (i, (atom, object)) in atoms.iter().zip(atom_objects).enumerate() {
+            let p = [atom[0], atom[1], atom[2]];
+            if current != Some(object) {
+                let (name, material) = &objects[object as usize];
+                ranges.push(SeedObject::new(name, i as u64, 0, *material, p, p));
+                current = Some(object);
+            }
+            let Some(range) = ranges.last_mut() else { continue };
+            range.atom_count += 1;
+            range.min = Vec3::from(range.min).min(Vec3::from(p)).to_array();
+            range.max = Vec3::from(range.max).max(Vec3::from(p)).to_array();
+        }
+        ranges
+    }
+
+    /// Échantillonne les textures de chaque atome à l'UV de son triangle source
+    fn sample_textures(raw: &RawScene, triangles: &[[f32; 6]], atoms: &[[f32; 6]], sources: &[u32]) -> AtomTexels {
+        let has_uvs = raw.triangle_uvs.iter().any(Option::is_some);
+        let has_colors = raw.triangle_colors.iter().any(Option::is_some);
+        if triangles.is_empty() || (!has_uvs && !has_colors) {
+            return AtomTexels::default();
+        }
+        let any = |pick: fn(&MaterialTextures) -> bool| has_uvs && raw.textures.iter().any(pick);
+        let corners = |tri: u32| {
+            let t = &triangles[tri as usize * 3..tri as usize * 3 + 3];
+            [0, 1, 2].map(|k| Vec3::new(t[k][0], t[k][1], t[k][2]))
         };
-        
-        let header_bytes = bincode::serialize(&header).unwrap();
-        file.write_all(&header_bytes).unwrap();
 
-        // Data (Positions + Normales)
-        let geo_bytes = bytemuck::cast_slice(&vertices);
-        file.write_all(geo_bytes).unwrap();
+        // UV de chaque atome (None si son triangle n'a pas d'UV)
+        let uvs: Vec<Option<Vec2>> = atoms.iter().zip(sources).map(|(atom, &tri)| {
+            let uvs = raw.triangle_uvs[tri as usize]?;
+            Some(interpolate_uv(Vec3::new(atom[0], atom[1], atom[2]), corners(tri), uvs))
+        }).collect();
+
+        // Valeur texturée si possible, constante du matériau sinon
+        let channel = |pick: fn(&MaterialTextures) -> Option<&Texture>, fallback: fn(&MaterialData) -> [f32; 4]| {
+            sources.iter().zip(&uvs).map(|(&tri, uv)| {
+                let mat = raw.triangle_materials[tri as usize] as usize;
+                match (pick(&raw.textures[mat]), uv) {
+                    (Some(texture), Some(uv)) => texture.sample(*uv),
+                    _ => fallback(&raw.materials[mat]),
+                }
+            }).collect::<Vec<_>>()
+        };
+
+        AtomTexels {
+            color: (has_colors || any(|t| t.diffuse.is_some())).then(|| {
+                let base = channel(|t| t.diffuse.as_ref(), |m| [m.base_color[0], m.base_color[1], m.base_color[2], 1.0]);
+                base.into_iter().zip(atoms.iter().zip(sources)).map(|(c, (atom, &tri))| {
+                    // Couleur de sommet interpolée (facteur 1 si le triangle n'en a pas)
+                    let tint = raw.triangle_colors[tri as usize].map_or(Vec3::ONE, |colors| {
+                        let w = barycentric(Vec3::new(atom[0], atom[1], atom[2]), corners(tri));
+                        colors[0] * w[0] + colors[1] * w[1] + colors[2] * w[2]
+                    });
+                    [c[0] * tint.x, c[1] * tint.y, c[2] * tint.z]
+                }).collect()
+            }),
+            roughness: any(|t| t.roughness.is_some()).then(|| {
+                channel(|t| t.roughness.as_ref(), |m| [m.roughness; 4]).into_iter().map(|c| c[0]).collect()
+            }),
+            metallic: any(|t| t.metallic.is_some()).then(|| {
+                channel(|t| t.metallic.as_ref(), |m| [m.metallic; 4]).into_iter().map(|c| c[0]).collect()
+            }),
+        }
     }
 
-    fn load_raw_obj(path: &str) -> (Vec<f32>, Vec<MaterialData>) {
+    /// OBJ triangulé, matériaux MTL et leurs textures.
+    /// Les maillages sans normales reçoivent des normales lissées (`crease_angle` en degrés).
+    fn load_raw_obj(path: &str, crease_angle: f32) -> RawScene {
         let load_options = tobj::LoadOptions {
             single_index: true,
             triangulate: true,
@@ -86,46 +586,121 @@ impl SeedImporter {
         let (models, materials_result) = tobj::load_obj(path, &load_options)
             .expect("❌ Erreur Ingestion OBJ (Vérifiez le chemin)");
 
-        // Gestion Placeholder des matériaux
-        let materials = match materials_result {
-            Ok(mats) => mats.into_iter().map(|m| MaterialData {
-                base_color: m.diffuse.unwrap_or([1.0, 0.84, 0.0]),
-                metallic: 1.0,
-                emissive_ptr: 0,
-                roughness: 0.3,
-                ior: m.optical_density.unwrap_or(1.45),
-                _padding: [0, 0],
-            }).collect(),
-            Err(_) => vec![MaterialData {
-                base_color: [1.0, 0.84, 0.0],
-                metallic: 1.0,
-                emissive_ptr: 0,
-                roughness: 0.3,
-                ior: 1.45,
-                _padding: [0, 0],
-            }],
+        let dir = Path::new(path).parent().unwrap_or(Path::new(""));
+        let (materials, textures): (Vec<MaterialData>, Vec<MaterialTextures>) = match materials_result {
+            Ok(mats) if !mats.is_empty() => mats.iter()
+                .map(|m| (MaterialData::from_mtl(m), MaterialTextures::load(m, dir)))
+                .unzip(),
+            _ => (vec![MaterialData::GOLD], vec![MaterialTextures::default()]),
+        };
+
+        // Groupes de lissage : tobj les ignore, on les relit dans le fichier.
+        // Les triangles de tobj suivent l'ordre des faces du fichier ; si le
+        // décompte diverge, tout le maillage est lissé (groupe 1).
+        let scan = Self::scan_obj(path);
+        let triangle_count: usize = models.iter().map(|m| m.mesh.indices.len() / 3).sum();
+        let smoothing = if scan.smoothing.len() == triangle_count {
+            scan.smoothing
+        } else {
+            vec![1; triangle_count]
         };
 
         let mut geometry = Vec::new();
+        let mut triangle_materials = Vec::new();
+        let mut triangle_objects = Vec::new();
+        let mut objects = Vec::new();
+        let mut triangle_uvs = Vec::new();
+        let mut first_triangle = 0;
         for model in models {
             let mesh = model.mesh;
-            for index in mesh.indices {
-                let i = index as usize;
-                // Position
-                geometry.push(mesh.positions[3 * i]);
-                geometry.push(mesh.positions[3 * i + 1]);
-                geometry.push(mesh.positions[3 * i + 2]);
-
-                // Normale
-                if !mesh.normals.is_empty() {
-                    geometry.push(mesh.normals[3 * i]);
-                    geometry.push(mesh.normals[3 * i + 1]);
-                    geometry.push(mesh.normals[3 * i + 2]);
-                } else {
-                    geometry.push(0.0); geometry.push(1.0); geometry.push(0.0);
+            let tri_count = mesh.indices.len() / 3;
+            let material = mesh.material_id
+                .filter(|&id| id < materials.len())
+                .unwrap_or(0) as u32;
+            triangle_materials.extend(std::iter::repeat_n(material, tri_count));
+            // Un modèle tobj = un objet/groupe OBJ avec un seul matériau
+            triangle_objects.extend(std::iter::repeat_n(objects.len() as u32, tri_count));
+            objects.push((model.name, material));
+
+            let has_uvs = !mesh.texcoords.is_empty();
+            for tri in mesh.indices.chunks_exact(3) {
+                triangle_uvs.push(has_uvs.then(|| {
+                    [0, 1, 2].map(|k| {
+                        let i = tri[k] as usize;
+                        Vec2::new(mesh.texcoords[2 * i], mesh.texcoords[2 * i + 1])
+                    })
+                }));
+            }
+
+            let corners: Vec<Vec3> = mesh.indices.iter()
+                .map(|&i| Vec3::from_slice(&mesh.positions[3 * i as usize..3 * i as usize + 3]))
+                .collect();
+            // Normales du fichier si présentes, sinon lissage aire × angle
+            let normals: Vec<Vec3> = if !mesh.normals.is_empty() {
+                mesh.indices.iter()
+                    .map(|&i| Vec3::from_slice(&mesh.normals[3 * i as usize..3 * i as usize + 3]))
+                    .collect()
+            } else {
+                let groups = &smoothing[first_triangle..first_triangle + tri_count];
+                vertex_normals(&corners, groups, crease_angle)
+            };
+            first_triangle += tri_count;
+
+            for (p, n) in corners.iter().zip(&normals) {
+                // Position + Normale (Y par défaut pour les faces dégénérées)
+                let n = if *n == Vec3::ZERO { Vec3::Y } else { *n };
+                geometry.extend_from_slice(&[p.x, p.y, p.z, n.x, n.y, n.z]);
+            }
+        }
+
+        // Aucune face : nuage de points (un seul objet)
+        let points = if geometry.is_empty() {
+            objects = vec![("points".to_string(), 0)];
+            let normals = point_normals(&scan.points);
+            scan.points.iter().zip(&normals)
+                .map(|(p, n)| [p.x, p.y, p.z, n.x, n.y, n.z])
+                .collect()
+        } else {
+            Vec::new()
+        };
+
+        let triangle_colors = vec![None; triangle_materials.len()];
+        RawScene {
+            points, geometry, triangle_materials, triangle_objects, objects, triangle_uvs, triangle_colors, materials, textures,
+            point_attributes: Vec::new(),
+            origin: None,
+            splats: false,
+        }
+    }
+
+    /// Lecture directe de ce que tobj ne conserve pas :
+    /// groupe de lissage de chaque triangle (ordre du fichier) et sommets bruts
+    fn scan_obj(path: &str) -> ObjScan {
+        let mut scan = ObjScan::default();
+        let Ok(source) = std::fs::read_to_string(path) else {
+            return scan;
+        };
+        let mut group = 1; // Sans `s`, tout est lissé
+        for line in source.lines() {
+            let mut words = line.split_whitespace();
+            match words.next() {
+                Some("s") => {
+                    group = words.next().and_then(|w| w.parse().ok()).unwrap_or(0); // "off" = 0
                 }
+                Some("f") | Some("l") => {
+                    // Triangulation en éventail (comme tobj) ; point et segment = 1 triangle dégénéré
+                    let arity = words.count();
+                    scan.smoothing.extend(std::iter::repeat_n(group, arity.saturating_sub(2).max(1)));
+                }
+                Some("v") => {
+                    let xyz: Vec<f32> = words.take(3).filter_map(|w| w.parse().ok()).collect();
+                    if let [x, y, z] = xyz[..] {
+                        scan.points.push(Vec3::new(x, y, z));
+                    }
+                }
+                _ => {}
             }
         }
-        (geometry, materials)
+        scan
     }
-}
\ No newline at end of file
+}
@@ -1,18 +1,26 @@
-use serde::{Serialize, Deserialize};
-
+pub mod format;
 pub mod importer;
+pub mod gltf_importer;
+pub mod assimp_importer;
+pub mod ply;
+pub mod lidar;
+pub mod splat;
+pub mod reader;
+pub mod quantize;
+pub mod sampling;
+pub mod voxelize;
+pub mod bvh;
+pub mod texture;
+pub mod normals;
 pub mod abc_loader;
+pub mod anim;
+pub mod asset_db;
 
-#[repr(C)]
-#[derive(Serialize, Deserialize, Debug)]
-pub struct SeedFileHeader {
-    pub magic: [u8; 4],      // "SEED"
-    pub version: u32,        // 2030
-    pub vertex_count: u64,   // Nombre d'atomes
-    pub index_count: u64,    // Reservé
-    pub bvh_offset: u64,     // Reservé pour l'accélération
-    pub material_ptr: u64,   // Reservé
-}
+pub use format::{SeedFileHeader, SeedWriter, ChunkKind, ChunkEntry, OrderingInfo, SeedObject, SpaceCurve, WorldOrigin};
+pub use reader::{SeedReader, SeedError};
+pub use quantize::AtomLayout;
+pub use sampling::SamplingMode;
+pub use bvh::BvhNode;
 
 /// Encodeur Morton 3D (Z-Order Curve)
 /// Entrelace les bits de X, Y, Z pour garantir que des points proches 
@@ -33,4 +41,52 @@ fn expand_bits(v: u32) -> u64 {
     v = (v | (v << 4))  & 0x10C30C30C30C30C3;
     v = (v | (v << 2))  & 0x1249249249249249;
     v
-}
\ No newline at end of file
+}
+
+/// Encodeur Hilbert 3D (algorithme de Skilling, `bits` bits par axe).
+/// Plus coûteux que Morton mais sans les grands sauts du Z-Order :
+/// deux clés consécutives sont toujours voisines dans l'espace.
+pub fn encode_hilbert_3d(x: u32, y: u32, z: u32, bits: u32) -> u64 {
+    let mut axes = [x, y, z];
+    let top = 1u32 << (bits - 1);
+
+    // 1. Axes -> forme "transposée"
+    let mut q = top;
+    while q > 1 {
+        let p = q - 1;
+        for i in 0..3 {
+            if axes[i] & q != 0 {
+                axes[0] ^= p;
+            } else {
+                let t = (axes[0] ^ axes[i]) & p;
+                axes[0] ^= t;
+                axes[i] ^= t;
+            }
+        }
+        q >>= 1;
+    }
+
+    // 2. Code de Gray
+    axes[1] ^= axes[0];
+    axes[2] ^= axes[1];
+    let mut t = 0;
+    let mut q = top;
+    while q > 1 {
+        if axes[2] & q != 0 {
+            t ^= q - 1;
+        }
+        q >>= 1;
+    }
+    for axis in &mut axes {
+        *axis ^= t;
+    }
+
+    // 3. Entrelacement (bit fort de X en tête)
+    let mut key = 0u64;
+    for bit in (0..bits).rev() {
+        for axis in axes {
+            key = (key << 1) | ((axis >> bit) & 1) as u64;
+        }
+    }
+    key
+}
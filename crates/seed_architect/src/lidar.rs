@@ -0,0 +1,95 @@
+// crates/seed_architect/src/lidar.rs
+//
+// LiDAR LAS / LAZ (crate `las`, décompression LAZ en Rust pur).
+// Les coordonnées d'un relevé (UTM, Lambert...) dépassent largement ce qu'un
+// f32 représente au millimètre : on choisit une origine en double précision
+// (centre de l'emprise, arrondi au mètre), stockée dans le chunk ORIG, et les
+// atomes sont relatifs à elle. 5 km autour de l'origine -> pas f32 < 0.5 mm.
+//
+// Attributs : "intensity", "classification" (code ASPRS), "color" si le format
+// de points en porte. Normales par PCA, orientées vers le haut (+Z, convention LAS).
+
+use crate::importer::{RawScene, COLOR_ATTRIBUTE};
+use crate::normals::point_normals;
+use crate::texture::srgb_to_linear;
+use dream_core::types::WorldPos;
+use glam::Vec3;
+use log::info;
+use std::path::Path;
+
+/// Attributs ATTR des relevés LiDAR
+pub const INTENSITY_ATTRIBUTE: &str = "intensity";
+pub const CLASSIFICATION_ATTRIBUTE: &str = "classification";
+
+pub struct LasImporter;
+
+impl LasImporter {
+    pub fn handles(path: &str) -> bool {
+        Path::new(path).extension()
+            .and_then(|e| e.to_str())
+            .is_some_and(|e| e.eq_ignore_ascii_case("las") || e.eq_ignore_ascii_case("laz"))
+    }
+
+    pub(crate) fn load_raw_las(path: &str) -> RawScene {
+        let mut reader = las::Reader::from_path(path)
+            .unwrap_or_else(|e| panic!("❌ Erreur Ingestion LAS {} : {}", path, e));
+        let header = reader.header();
+        let transforms = header.transforms();
+        info!(
+            "📡 LAS {} : {} points, échelle ({}, {}, {}), décalage ({}, {}, {})",
+            path, header.number_of_points(),
+            transforms.x.scale, transforms.y.scale, transforms.z.scale,
+            transforms.x.offset, transforms.y.offset, transforms.z.offset,
+        );
+        // Centre de l'emprise du header, arrondi au mètre pour rester lisible
+        let bounds = header.bounds();
+        let min = WorldPos::new(bounds.min.x, bounds.min.y, bounds.min.z);
+        let max = WorldPos::new(bounds.max.x, bounds.max.y, bounds.max.z);
+        let origin = ((min + max) * 0.5).round();
+
+        let count = header.number_of_points() as usize;
+        let has_color = header.point_format().has_color;
+
+        // Un seul passage sur les points, sans garder les `las::Point` (plusieurs
+        // fois la taille d'un atome) : chaque champ va directement dans son buffer.
+        // Le crate applique échelle et décalage : x/y/z sont déjà en coordonnées réelles
+        let mut positions: Vec<Vec3> = Vec::with_capacity(count);
+        let mut intensities: Vec<f32> = Vec::with_capacity(count);
+        let mut classifications: Vec<f32> = Vec::with_capacity(count);
+        let mut colors: Vec<f32> = Vec::with_capacity(if has_color { count * 3 } else { 0 });
+        let mut max_color = 0u16;
+        for point in reader.points() {
+            let p = point.unwrap_or_else(|e| panic!("❌ Erreur lecture des points LAS {} : {}", path, e));
+            positions.push((WorldPos::new(p.x, p.y, p.z) - origin).as_vec3());
+            intensities.push(p.intensity as f32);
+            classifications.push(u8::from(p.classification) as f32);
+            if let Some(c) = p.color.filter(|_| has_color) {
+                max_color = max_color.max(c.red).max(c.green).max(c.blue);
+                colors.extend([c.red as f32, c.green as f32, c.blue as f32]);
+            }
+        }
+        let normals: Vec<Vec3> = point_normals(&positions).into_iter()
+            .map(|n| if n.z < 0.0 { -n } else { n })
+            .collect();
+
+        let mut raw = RawScene {
+            points: positions.iter().zip(&normals)
+                .map(|(p, n)| [p.x, p.y, p.z, n.x, n.y, n.z])
+                .collect(),
+            objects: vec![("points".to_string(), 0)],
+            origin: Some(origin),
+            ..Default::default()
+        };
+        raw.point_attributes.push((INTENSITY_ATTRIBUTE.to_string(), 1, intensities));
+        raw.point_attributes.push((CLASSIFICATION_ATTRIBUTE.to_string(), 1, classifications));
+
+        if has_color && colors.len() == positions.len() * 3 {
+            // La norme impose 16 bits, mais beaucoup d'outils écrivent des valeurs 8 bits
+            let scale = if max_color <= 255 { 255.0 } else { 65535.0 };
+            // Couleurs du relevé en sRGB : "color" est linéaire
+            colors.iter_mut().for_each(|c| *c = srgb_to_linear(*c / scale));
+            raw.point_attributes.push((COLOR_ATTRIBUTE.to_string(), 3, colors));
+        }
+        raw
+    }
+}
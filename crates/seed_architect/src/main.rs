@@ -1,31 +1,652 @@
-use seed_architect::importer::SeedImporter;
-use log::info;
-use std::path::Path;
+// crates/seed_architect/src/main.rs
+//
+// Ligne de commande du baker :
+//   bake <source> [-o <sortie>]   une source -> .seed (.seedanim pour un Alembic)
+//   batch <glob>                  toutes les sources d'un motif
+//   info <fichier>                en-tête, chunks, objets d'un .seed / .seedanim
+//   watch <dossier>               re-bake dès qu'une source ou une dépendance change
+//   build                         re-bake les assets périmés de assets/raw, GC de assets/processed
+//
+// Chaque bake réussi est enregistré dans la base d'assets (--db) : build et watch
+// s'en servent pour ne re-baker que ce qui est périmé.
+// Avec --json, chaque commande écrit son résultat en JSON sur stdout (une ligne
+// par bake pour watch) ; les logs restent sur stderr.
+// Codes de sortie : 0 = succès, 1 = au moins un échec, 2 = usage invalide.
 
-fn main() {
-    // Initialisation des logs pour voir ce qu'il se passe
+use seed_architect::abc_loader::AbcStream;
+use seed_architect::anim::{AnimReader, AnimSettings};
+use seed_architect::asset_db::{dependencies, AssetDatabase, AssetSettings, SourceSnapshot, Staleness, ASSET_DATABASE};
+use seed_architect::importer::{BakeSettings, SeedImporter};
+use seed_architect::{AtomLayout, SamplingMode, SeedReader, SpaceCurve};
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use log::{error, info, warn};
+use serde::Serialize;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::time::{Duration, Instant, SystemTime};
+
+/// Au moins un bake ou une lecture a échoué
+const EXIT_FAILURE: u8 = 1;
+
+#[derive(Parser)]
+#[command(name = "seed_architect", version, about = "Baker .seed / .seedanim")]
+struct Cli {
+    /// Résultats en JSON sur stdout (pour les scripts du pipeline)
+    #[arg(long, global = true)]
+    json: bool,
+    /// Base d'assets où sont enregistrés les bakes
+    #[arg(long, global = true, default_value = ASSET_DATABASE)]
+    db: PathBuf,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Bake une source (OBJ, glTF, PLY, LAS/LAZ, formats Assimp, Alembic)
+    Bake {
+        input: PathBuf,
+        /// Fichier produit (défaut : assets/processed/<nom>.seed ou .seedanim)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+        #[command(flatten)]
+        options: BakeOptions,
+    },
+    /// Bake toutes les sources d'un motif glob (ex. "assets/raw/*.obj")
+    Batch {
+        pattern: String,
+        #[arg(long, default_value = "assets/processed")]
+        out_dir: PathBuf,
+        #[command(flatten)]
+        options: BakeOptions,
+    },
+    /// Décrit un .seed ou un .seedanim
+    Info { path: PathBuf },
+    /// Surveille un dossier et re-bake les sources modifiées (ou leurs .mtl / textures)
+    Watch {
+        dir: PathBuf,
+        #[arg(long, default_value = "assets/processed")]
+        out_dir: PathBuf,
+        /// Période de scrutation (ms)
+        #[arg(long, default_value_t = 500)]
+        interval: u64,
+        #[command(flatten)]
+        options: BakeOptions,
+    },
+    /// Re-bake les sources périmées de `raw_dir` et les autres assets enregistrés,
+    /// puis supprime les fichiers de `out_dir` dont la source enregistrée a disparu
+    Build {
+        #[arg(long, default_value = "assets/raw")]
+        raw_dir: PathBuf,
+        #[arg(long, default_value = "assets/processed")]
+        out_dir: PathBuf,
+        /// Liste ce qui serait re-baké ou supprimé, sans rien toucher
+        #[arg(long)]
+        dry_run: bool,
+        /// Garde les fichiers orphelins
+        #[arg(long)]
+        no_gc: bool,
+        /// Supprime aussi les fichiers produits que la base ne connaît pas
+        #[arg(long, conflicts_with = "no_gc")]
+        gc_untracked: bool,
+        #[command(flatten)]
+        options: BakeOptions,
+    },
+}
+
+/// Réglages du bake (BakeSettings, AnimSettings pour un Alembic)
+#[derive(Args, Clone, Debug)]
+struct BakeOptions {
+    /// Graine du générateur (jitter, échantillonnage)
+    #[arg(long, default_value_t = 0)]
+    seed: u64,
+    /// Niveau zstd (absent : chunks bruts pour un .seed, niveau 3 pour un .seedanim)
+    #[arg(long)]
+    compression: Option<i32>,
+    /// Atomes quantifiés (12 octets au lieu de 24)
+    #[arg(long)]
+    quantized: bool,
+    #[arg(long, value_enum, default_value_t = Sampling::Vertices)]
+    sampling: Sampling,
+    /// Atomes par unité² des échantillonnages uniform et poisson
+    #[arg(long, default_value_t = 100.0)]
+    density: f32,
+    /// Courbe du tri spatial des atomes
+    #[arg(long, value_enum, default_value_t = Curve::Morton)]
+    ordering: Curve,
+    /// Remplissage volumique (voxels sur le plus grand axe, maillage fermé)
+    #[arg(long)]
+    voxel_fill: Option<u32>,
+    /// Angle de pli (degrés) des normales calculées
+    #[arg(long, default_value_t = 60.0)]
+    crease_angle: f32,
+    /// Alembic : erreur maximale tolérée sur une position (unités monde)
+    #[arg(long, default_value_t = 1e-4)]
+    tolerance: f32,
+}
+
+#[derive(Clone, Copy, Debug, ValueEnum)]
+enum Sampling {
+    /// Un atome par sommet
+    Vertices,
+    /// Tirage uniforme pondéré par l'aire
+    Uniform,
+    /// Bruit bleu (Poisson-disk)
+    Poisson,
+}
+
+#[derive(Clone, Copy, Debug, ValueEnum)]
+enum Curve {
+    Morton,
+    Hilbert,
+}
+
+impl BakeOptions {
+    fn settings(&self) -> BakeSettings {
+        BakeSettings {
+            seed: self.seed,
+            compression_level: self.compression,
+            atom_layout: if self.quantized { AtomLayout::Quantized } else { AtomLayout::Full },
+            sampling: match self.sampling {
+                Sampling::Vertices => SamplingMode::Vertices,
+                Sampling::Uniform => SamplingMode::Uniform { density: self.density },
+                Sampling::Poisson => SamplingMode::PoissonDisk { density: self.density },
+            },
+            ordering: match self.ordering {
+                Curve::Morton => SpaceCurve::Morton,
+                Curve::Hilbert => SpaceCurve::Hilbert,
+            },
+            voxel_fill: self.voxel_fill,
+            crease_angle: self.crease_angle,
+        }
+    }
+
+    fn anim_settings(&self) -> AnimSettings {
+        let defaults = AnimSettings::default();
+        AnimSettings {
+            tolerance: self.tolerance,
+            compression_level: self.compression.unwrap_or(defaults.compression_level),
+        }
+    }
+
+    fn for_source(&self, source: &Path) -> AssetSettings {
+        AssetSettings::for_source(&source.to_string_lossy(), &self.settings(), &self.anim_settings())
+    }
+}
+
+/// Résultat d'un bake
+#[derive(Serialize)]
+struct BakeReport {
+    input: String,
+    output: String,
+    ok: bool,
+    /// Pourquoi l'asset était périmé (build, watch)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    reason: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+    seconds: f64,
+    /// Taille du fichier produit (octets)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    bytes: Option<u64>,
+}
+
+#[derive(Serialize)]
+struct BatchReport {
+    baked: usize,
+    failed: usize,
+    results: Vec<BakeReport>,
+}
+
+#[derive(Serialize)]
+struct BuildReport {
+    baked: usize,
+    failed: usize,
+    up_to_date: usize,
+    /// Bakes effectués (ou prévus avec --dry-run)
+    results: Vec<BakeReport>,
+    /// Orphelins supprimés (ou à supprimer avec --dry-run)
+    removed: Vec<String>,
+    dry_run: bool,
+}
+
+/// Sortie de `info`
+#[derive(Serialize)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+enum FileInfo {
+    Seed {
+        path: String,
+        version: u32,
+        atoms: u64,
+        atom_layout: String,
+        splats: bool,
+        content_hash: String,
+        stored_bytes: u64,
+        raw_bytes: u64,
+        checksums_ok: bool,
+        source: Option<String>,
+        generator: Option<String>,
+        materials: usize,
+        chunks: Vec<ChunkInfo>,
+        objects: Vec<ObjectInfo>,
+        attributes: Vec<AttributeInfo>,
+    },
+    Seedanim {
+        path: String,
+        version: u32,
+        atoms: u64,
+        frames: u32,
+        keyframes: usize,
+        fps: f32,
+        duration: f64,
+        velocities: bool,
+        content_hash: String,
+        bytes: u64,
+        objects: Vec<ObjectInfo>,
+    },
+}
+
+#[derive(Serialize)]
+struct ChunkInfo {
+    kind: String,
+    version: u32,
+    count: u64,
+    size: u64,
+    raw_size: u64,
+    compressed: bool,
+}
+
+#[derive(Serialize)]
+struct ObjectInfo {
+    name: String,
+    first_atom: u64,
+    atom_count: u64,
+    material: u32,
+}
+
+#[derive(Serialize)]
+struct AttributeInfo {
+    name: String,
+    components: u32,
+}
+
+#[derive(Serialize)]
+struct ErrorReport {
+    path: String,
+    error: String,
+}
+
+fn main() -> ExitCode {
+    // Logs sur stderr : stdout est réservé au JSON
     env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("info")).init();
-    
-    info!("🏗️ [ARCHITECT] Démarrage du protocole de baking...");
+    // Les importeurs paniquent sur une source invalide : le message passe par les logs
+    // et le bake est compté comme un échec au lieu d'interrompre un batch
+    std::panic::set_hook(Box::new(|panic| error!("{}", panic_message(panic.payload()))));
+
+    let cli = Cli::parse();
+    // Base illisible : on repart de zéro, tout sera simplement re-baké (sans GC)
+    let mut db = AssetDatabase::open(&cli.db).unwrap_or_else(|e| {
+        warn!("⚠️ Base d'assets {} : {} (reconstruite)", cli.db.display(), e);
+        AssetDatabase::reset(&cli.db)
+    });
+    let ok = match cli.command {
+        Command::Bake { input, output, options } => {
+            let output = output.unwrap_or_else(|| default_output(&input, Path::new("assets/processed")));
+            let report = bake(&mut db, &input, &output, &options.for_source(&input), None);
+            let ok = report.ok;
+            emit(cli.json, &report);
+            ok
+        }
+        Command::Batch { pattern, out_dir, options } => batch(&mut db, &pattern, &out_dir, &options, cli.json),
+        Command::Info { path } => info(&path, cli.json),
+        Command::Watch { dir, out_dir, interval, options } => {
+            watch(&mut db, &dir, &out_dir, Duration::from_millis(interval), &options, cli.json)
+        }
+        Command::Build { raw_dir, out_dir, dry_run, no_gc, gc_untracked, options } => {
+            let gc = match (no_gc, gc_untracked) {
+                (true, _) => None,
+                (false, untracked) => Some(untracked),
+            };
+            build(&mut db, &raw_dir, &out_dir, &options, dry_run, gc, cli.json)
+        }
+    };
+    let ok = save(&db) && ok;
+    if ok { ExitCode::SUCCESS } else { ExitCode::from(EXIT_FAILURE) }
+}
+
+fn save(db: &AssetDatabase) -> bool {
+    db.save().map_err(|e| error!("❌ Écriture de la base d'assets {} : {}", db.path().display(), e)).is_ok()
+}
+
+/// JSON sur stdout si demandé (les logs ont déjà décrit le résultat sinon)
+fn emit<T: Serialize>(json: bool, value: &T) {
+    if json {
+        println!("{}", serde_json::to_string(value).expect("❌ Sérialisation JSON"));
+    }
+}
+
+fn panic_message(payload: &(dyn std::any::Any + Send)) -> String {
+    payload.downcast_ref::<&str>().map(|s| s.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "panic".to_string())
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Source qu'un importeur (ou le lecteur Alembic) sait lire
+fn is_source(path: &Path) -> bool {
+    let path = path.to_string_lossy();
+    SeedImporter::handles(&path) || AbcStream::handles(&path)
+}
 
-    // Chemins (Universels)
-    let input_path = "assets/raw/relic_test.obj";
-    let output_dir = "assets/processed";
-    let output_path = "assets/processed/relic.seed";
+/// `<out_dir>/<nom>.seed`, ou `.seedanim` pour un Alembic
+fn default_output(input: &Path, out_dir: &Path) -> PathBuf {
+    let extension = if AbcStream::handles(&input.to_string_lossy()) { "seedanim" } else { "seed" };
+    let stem = input.file_stem().unwrap_or(input.as_os_str());
+    out_dir.join(stem).with_extension(extension)
+}
+
+/// Bake d'une source, erreurs et panics des importeurs comprises.
+/// Un bake réussi est enregistré dans la base.
+fn bake(db: &mut AssetDatabase, input: &Path, output: &Path, settings: &AssetSettings, reason: Option<Staleness>) -> BakeReport {
+    let start = Instant::now();
+    let (input_str, output_str) = (input.to_string_lossy().into_owned(), output.to_string_lossy().into_owned());
+    match &reason {
+        Some(reason) => info!("🔥 Baking : {} -> {} ({})", input_str, output_str, reason),
+        None => info!("🔥 Baking : {} -> {}", input_str, output_str),
+    }
+
+    let result = if !input.is_file() {
+        Err(format!("source not found: {}", input_str))
+    } else if !is_source(input) {
+        Err(format!("unsupported source format: {}", input_str))
+    } else {
+        if let Some(dir) = output.parent().filter(|d| !d.as_os_str().is_empty()) {
+            std::fs::create_dir_all(dir).unwrap_or_else(|e| warn!("⚠️ Dossier {} : {}", dir.display(), e));
+        }
+        let snapshot = SourceSnapshot::of(&input_str);
+        catch_unwind(AssertUnwindSafe(|| settings.bake(&input_str, &output_str).map_err(|e| e.to_string())))
+            .unwrap_or_else(|panic| Err(panic_message(panic.as_ref())))
+            .map(|()| snapshot)
+    };
+    let result = result.map(|snapshot| db.record(snapshot, &output_str, settings));
+
+    let report = BakeReport {
+        input: input_str,
+        output: output_str,
+        ok: result.is_ok(),
+        reason: reason.map(|r| r.to_string()),
+        seconds: start.elapsed().as_secs_f64(),
+        bytes: result.is_ok().then(|| std::fs::metadata(output).map(|m| m.len()).ok()).flatten(),
+        error: result.err(),
+    };
+    match &report.error {
+        None => info!("✅ {} ({:.2} s)", report.output, report.seconds),
+        Some(e) => error!("❌ Échec du bake de {} : {}", report.input, e),
+    }
+    report
+}
+
+fn batch(db: &mut AssetDatabase, pattern: &str, out_dir: &Path, options: &BakeOptions, json: bool) -> bool {
+    let paths = match glob::glob(pattern) {
+        Ok(paths) => paths,
+        Err(e) => {
+            error!("❌ Motif invalide `{}` : {}", pattern, e);
+            emit(json, &ErrorReport { path: pattern.to_string(), error: e.to_string() });
+            return false;
+        }
+    };
+    let sources: Vec<PathBuf> = paths.filter_map(Result::ok).filter(|p| p.is_file() && is_source(p)).collect();
+    if sources.is_empty() {
+        warn!("⚠️ Aucune source ne correspond à `{}`", pattern);
+    }
+
+    let results: Vec<BakeReport> = sources.iter()
+        .map(|input| bake(db, input, &default_output(input, out_dir), &options.for_source(input), None))
+        .collect();
+    let failed = results.iter().filter(|r| !r.ok).count();
+    info!("📦 Batch : {} bake(s), {} échec(s)", results.len() - failed, failed);
+    emit(json, &BatchReport { baked: results.len() - failed, failed, results });
+    failed == 0
+}
+
+fn info(path: &Path, json: bool) -> bool {
+    let described = if path.extension().is_some_and(|e| e.eq_ignore_ascii_case("seedanim")) {
+        describe_anim(path)
+    } else {
+        describe_seed(path)
+    };
+    match described {
+        Ok(described) => {
+            if json {
+                emit(json, &described);
+            } else {
+                // Lecture humaine : le même contenu, indenté
+                println!("{}", serde_json::to_string_pretty(&described).expect("❌ Sérialisation JSON"));
+            }
+            true
+        }
+        Err(e) => {
+            error!("❌ Lecture de {} : {}", path.display(), e);
+            emit(json, &ErrorReport { path: path.to_string_lossy().into_owned(), error: e });
+            false
+        }
+    }
+}
+
+fn describe_seed(path: &Path) -> Result<FileInfo, String> {
+    let reader = SeedReader::open(path).map_err(|e| e.to_string())?;
+    let header = reader.header();
+    let (stored_bytes, raw_bytes) = reader.payload_sizes();
+    let metadata = reader.metadata().map_err(|e| e.to_string())?;
+    let objects = reader.objects().map_err(|e| e.to_string())?;
+    let attributes = reader.attributes().map_err(|e| e.to_string())?;
+
+    Ok(FileInfo::Seed {
+        path: path.to_string_lossy().into_owned(),
+        version: header.version,
+        atoms: reader.vertex_count(),
+        atom_layout: format!("{:?}", reader.atom_layout()),
+        splats: reader.is_splats(),
+        content_hash: hex(&reader.content_hash()),
+        stored_bytes,
+        raw_bytes,
+        checksums_ok: reader.verify_checksums().is_ok(),
+        source: metadata.map(|m| m.source.to_string()),
+        generator: metadata.map(|m| m.generator.to_string()),
+        materials: reader.materials().map_err(|e| e.to_string())?.len(),
+        chunks: reader.chunks().iter().map(|e| ChunkInfo {
+            kind: String::from_utf8_lossy(&e.kind.to_le_bytes()).into_owned(),
+            version: e.version,
+            count: e.count,
+            size: e.size,
+            raw_size: e.raw_size,
+            compressed: e.is_compressed(),
+        }).collect(),
+        objects: objects.iter().map(object_info).collect(),
+        attributes: attributes.iter().map(|(h, _)| AttributeInfo { name: h.name().to_string(), components: h.components }).collect(),
+    })
+}
+
+fn describe_anim(path: &Path) -> Result<FileInfo, String> {
+    let reader = AnimReader::open(path).map_err(|e| e.to_string())?;
+    Ok(FileInfo::Seedanim {
+        path: path.to_string_lossy().into_owned(),
+        version: reader.header().version,
+        atoms: reader.atom_count(),
+        frames: reader.frame_count(),
+        keyframes: reader.frames().iter().filter(|f| f.is_key()).count(),
+        fps: reader.fps(),
+        duration: reader.duration(),
+        velocities: reader.has_velocities(),
+        content_hash: hex(&reader.content_hash()),
+        bytes: std::fs::metadata(path).map(|m| m.len()).unwrap_or(0),
+        objects: reader.objects().iter().map(object_info).collect(),
+    })
+}
+
+fn object_info(object: &seed_architect::SeedObject) -> ObjectInfo {
+    ObjectInfo {
+        name: object.name().to_string(),
+        first_atom: object.first_atom,
+        atom_count: object.atom_count,
+        material: object.material,
+    }
+}
+
+/// Sources de `dir`, sous-dossiers compris
+fn scan_sources(dir: &Path, sources: &mut Vec<PathBuf>) {
+    let Ok(entries) = std::fs::read_dir(dir) else { return };
+    for path in entries.filter_map(Result::ok).map(|e| e.path()) {
+        if path.is_dir() {
+            scan_sources(&path, sources);
+        } else if is_source(&path) {
+            sources.push(path);
+        }
+    }
+}
+
+/// Date de modification de la source et de chacune de ses dépendances (None : absente)
+type Fingerprint = Vec<(PathBuf, Option<SystemTime>)>;
+
+fn fingerprint(source: &Path) -> Fingerprint {
+    let files = std::iter::once(source.to_path_buf()).chain(dependencies(&source.to_string_lossy()));
+    files.map(|f| {
+        let modified = std::fs::metadata(&f).and_then(|m| m.modified()).ok();
+        (f, modified)
+    }).collect()
+}
+
+/// Boucle de scrutation : une source est re-bakée quand sa date ou celle d'une
+/// dépendance change et que la base d'assets la dit périmée.
+/// Ne rend la main qu'en cas d'erreur sur le dossier surveillé.
+fn watch(db: &mut AssetDatabase, dir: &Path, out_dir: &Path, interval: Duration, options: &BakeOptions, json: bool) -> bool {
+    if !dir.is_dir() {
+        error!("❌ Dossier surveillé introuvable : {}", dir.display());
+        emit(json, &ErrorReport { path: dir.to_string_lossy().into_owned(), error: "directory not found".to_string() });
+        return false;
+    }
+    info!("👁️ Surveillance de {} -> {}", dir.display(), out_dir.display());
+
+    let mut known: Vec<(PathBuf, Fingerprint)> = Vec::new();
+    loop {
+        let mut sources = Vec::new();
+        scan_sources(dir, &mut sources);
+        known.retain(|(source, _)| sources.contains(source));
+
+        for source in sources {
+            let current = fingerprint(&source);
+            let changed = match known.iter_mut().find(|(s, _)| *s == source) {
+                Some((_, previous)) if *previous == current => false,
+                Some((_, previous)) => {
+                    *previous = current;
+                    true
+                }
+                None => {
+                    known.push((source.clone(), current));
+                    true
+                }
+            };
+            if !changed {
+                continue;
+            }
+            let output = default_output(&source, out_dir);
+            let settings = options.for_source(&source);
+            if let Some(reason) = db.staleness(&source.to_string_lossy(), &output.to_string_lossy(), &settings) {
+                emit(json, &bake(db, &source, &output, &settings, Some(reason)));
+                save(db);
+            }
+        }
+        std::thread::sleep(interval);
+    }
+}
+
+/// Re-bake ce qui est périmé : les sources de `raw_dir` (sortie et réglages par
+/// défaut), puis les autres assets enregistrés dont la source existe encore (avec
+/// leurs propres réglages). Les orphelins de `out_dir` sont ensuite supprimés si
+/// `gc` est donné (Some(true) : fichiers inconnus de la base compris).
+fn build(db: &mut AssetDatabase, raw_dir: &Path, out_dir: &Path, options: &BakeOptions, dry_run: bool, gc: Option<bool>, json: bool) -> bool {
+    let mut sources = Vec::new();
+    scan_sources(raw_dir, &mut sources);
+    sources.sort();
+
+    // (source, sortie, réglages) : d'abord assets/raw, puis le reste de la base
+    let mut assets: Vec<(String, String, AssetSettings)> = Vec::new();
+    for source in &sources {
+        let output = default_output(source, out_dir).to_string_lossy().into_owned();
+        if let Some((other, _, _)) = assets.iter().find(|(_, o, _)| *o == output) {
+            warn!("⚠️ {} et {} produisent tous deux {} : {} ignorée", other, source.display(), output, source.display());
+            continue;
+        }
+        assets.push((source.to_string_lossy().into_owned(), output, options.for_source(source)));
+    }
+    for record in db.records() {
+        if Path::new(&record.source.path).is_file() && !assets.iter().any(|(_, o, _)| *o == record.output) {
+            assets.push((record.source.path.clone(), record.output.clone(), record.settings.clone()));
+        }
+    }
 
-    // Vérification de l'entrée
-    if !Path::new(input_path).exists() {
-        panic!("❌ Fichier source introuvable : {}", input_path);
+    let mut results = Vec::new();
+    let mut up_to_date = 0;
+    for (source, output, settings) in &assets {
+        let Some(reason) = db.staleness(source, output, settings) else {
+            up_to_date += 1;
+            continue;
+        };
+        if dry_run {
+            info!("🔁 {} périmé : {}", output, reason);
+            results.push(BakeReport {
+                input: source.clone(),
+                output: output.clone(),
+                ok: true,
+                reason: Some(reason.to_string()),
+                error: None,
+                seconds: 0.0,
+                bytes: None,
+            });
+        } else {
+            results.push(bake(db, Path::new(source), Path::new(output), settings, Some(reason)));
+        }
     }
 
-    // Création du dossier de sortie si nécessaire
-    if !Path::new(output_dir).exists() {
-        std::fs::create_dir_all(output_dir).expect("❌ Impossible de créer le dossier assets/processed");
+    // GC : fichiers enregistrés dont la source a disparu, puis les entrées sans fichier
+    let mut removed = Vec::new();
+    if gc.is_some() && db.was_reset() {
+        warn!("⚠️ Base d'assets {} reconstruite : GC ignoré", db.path().display());
+    } else if let Some(untracked) = gc {
+        for orphan in db.orphans(out_dir, untracked) {
+            let orphan = orphan.to_string_lossy().into_owned();
+            if dry_run {
+                info!("🗑️ Orphelin : {}", orphan);
+            } else {
+                match std::fs::remove_file(&orphan) {
+                    Ok(()) => info!("🗑️ Orphelin supprimé : {}", orphan),
+                    Err(e) => {
+                        warn!("⚠️ Suppression de {} : {}", orphan, e);
+                        continue;
+                    }
+                }
+            }
+            removed.push(orphan);
+        }
+        if !dry_run {
+            // Une entrée dont le fichier n'a pu être supprimé reste : le prochain GC le reprendra
+            let gone: Vec<String> = db.records()
+                .filter(|r| !Path::new(&r.output).is_file())
+                .map(|r| r.output.clone())
+                .collect();
+            for output in gone {
+                db.remove(&output);
+            }
+        }
     }
 
-    // Lancement de la conversion
-    info!("🔥 Baking en cours : {} -> {}", input_path, output_path);
-    SeedImporter::import_and_bake(input_path, output_path);
-    
-    info!("✅ [ARCHITECT] Succès ! Fichier .seed prêt pour le Runtime.");
-}
\ No newline at end of file
+    let failed = results.iter().filter(|r| !r.ok).count();
+    let baked = if dry_run { 0 } else { results.len() - failed };
+    info!("📦 Build : {} re-baké(s), {} à jour, {} échec(s), {} orphelin(s)", baked, up_to_date, failed, removed.len());
+    emit(json, &BuildReport { baked, failed, up_to_date, results, removed, dry_run });
+    failed == 0
+}
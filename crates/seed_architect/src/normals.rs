@@ -0,0 +1,134 @@
+// crates/seed_architect/src/normals.rs
+//
+// Normales des sources qui n'en fournissent pas :
+//   - maillages : moyenne des normales de faces pondérée par l'aire et par
+//     l'angle au coin, limitée au groupe de lissage et à l'angle de pli
+//   - nuages de points : PCA sur les voisins (plus petite direction de variance)
+
+use glam::{IVec3, Mat3, Vec3};
+use std::collections::HashMap;
+
+/// Voisins utilisés pour la PCA d'un point
+const PCA_NEIGHBOURS: usize = 12;
+
+/// Normales par coin d'une soupe de triangles (3 coins consécutifs = 1 triangle).
+/// `groups[t]` = groupe de lissage du triangle `t` (0 = facettes plates).
+/// Deux faces d'un même groupe ne sont lissées que si leur angle est sous `crease_angle` (degrés).
+pub fn vertex_normals(corners: &[Vec3], groups: &[u32], crease_angle: f32) -> Vec<Vec3> {
+    let faces: Vec<Vec3> = corners.chunks_exact(3)
+        .map(|c| (c[1] - c[0]).cross(c[2] - c[0])) // Norme = 2 × aire
+        .collect();
+    let units: Vec<Vec3> = faces.iter().map(|f| f.normalize_or_zero()).collect();
+
+    // Contribution de chaque coin : normale × aire × angle au coin
+    let weighted: Vec<Vec3> = (0..corners.len()).map(|i| {
+        let (t, k) = (i / 3, i % 3);
+        let a = corners[t * 3 + (k + 1) % 3] - corners[i];
+        let b = corners[t * 3 + (k + 2) % 3] - corners[i];
+        let angle = if a == Vec3::ZERO || b == Vec3::ZERO { 0.0 } else { a.angle_between(b) };
+        faces[t] * angle
+    }).collect();
+
+    // Coins soudés par position exacte
+    let mut shared: HashMap<[u32; 3], Vec<usize>> = HashMap::new();
+    for (i, p) in corners.iter().enumerate() {
+        shared.entry(p.to_array().map(f32::to_bits)).or_default().push(i);
+    }
+
+    let cos_crease = crease_angle.to_radians().cos();
+    corners.iter().enumerate().map(|(i, p)| {
+        let t = i / 3;
+        let face = units[t];
+        if groups[t] == 0 && face != Vec3::ZERO {
+            return face;
+        }
+        // Faces du même groupe sous l'angle de pli. Un triangle dégénéré
+        // (pôle d'une sphère UV...) hérite de tous ses voisins du groupe.
+        shared[&p.to_array().map(f32::to_bits)].iter()
+            .filter(|&&j| groups[j / 3] == groups[t] && (face == Vec3::ZERO || units[j / 3].dot(face) >= cos_crease))
+            .map(|&j| weighted[j])
+            .sum::<Vec3>()
+            .try_normalize()
+            .unwrap_or(face)
+    }).collect()
+}
+
+/// Normales d'un nuage de points par PCA sur les `PCA_NEIGHBOURS` plus proches voisins,
+/// orientées à l'opposé du centroïde (heuristique pour les objets fermés).
+pub fn point_normals(points: &[Vec3]) -> Vec<Vec3> {
+    if points.len() < 3 {
+        return vec![Vec3::Y; points.len()];
+    }
+    let (min, max) = points.iter().fold(
+        (Vec3::splat(f32::MAX), Vec3::splat(f32::MIN)),
+        |(lo, hi), p| (lo.min(*p), hi.max(*p)),
+    );
+    let centroid = points.iter().sum::<Vec3>() / points.len() as f32;
+
+    // Grille de hachage : ~PCA_NEIGHBOURS points par cellule sur une surface
+    let cells_per_axis = (points.len() as f32 / PCA_NEIGHBOURS as f32).sqrt().max(1.0);
+    let cell = ((max - min).max_element() / cells_per_axis).max(f32::EPSILON);
+    let key = |p: Vec3| ((p - min) / cell).floor().as_ivec3();
+    let mut grid: HashMap<IVec3, Vec<usize>> = HashMap::new();
+    for (i, p) in points.iter().enumerate() {
+        grid.entry(key(*p)).or_default().push(i);
+    }
+
+    points.iter().map(|p| {
+        // Anneaux de cellules de plus en plus larges jusqu'à avoir assez de voisins
+        let center = key(*p);
+        let mut neighbours = Vec::new();
+        for ring in 1..=4 {
+            neighbours.clear();
+            for dz in -ring..=ring {
+                for dy in -ring..=ring {
+                    for dx in -ring..=ring {
+                        if let Some(ids) = grid.get(&(center + IVec3::new(dx, dy, dz))) {
+                            neighbours.extend(ids.iter().map(|&i| points[i]));
+                        }
+                    }
+                }
+            }
+            if neighbours.len() > PCA_NEIGHBOURS {
+                break;
+            }
+        }
+        neighbours.sort_by(|a, b| a.distance_squared(*p).total_cmp(&b.distance_squared(*p)));
+        neighbours.truncate(PCA_NEIGHBOURS + 1); // Le point lui-même + ses voisins
+
+        let n = smallest_eigenvector(covariance(&neighbours)).unwrap_or(Vec3::Y);
+        if n.dot(*p - centroid) < 0.0 { -n } else { n }
+    }).collect()
+}
+
+fn covariance(points: &[Vec3]) -> Mat3 {
+    let mean = points.iter().sum::<Vec3>() / points.len() as f32;
+    let mut cov = Mat3::ZERO;
+    for p in points {
+        let d = *p - mean;
+        cov += Mat3::from_cols(d * d.x, d * d.y, d * d.z);
+    }
+    cov
+}
+
+/// Vecteur propre de la plus petite valeur propre d'une matrice symétrique 3×3
+/// (valeurs propres analytiques, puis produit vectoriel des lignes de A - λI)
+fn smallest_eigenvector(a: Mat3) -> Option<Vec3> {
+    let q = (a.x_axis.x + a.y_axis.y + a.z_axis.z) / 3.0;
+    let p1 = a.y_axis.x.powi(2) + a.z_axis.x.powi(2) + a.z_axis.y.powi(2);
+    let p2 = (a.x_axis.x - q).powi(2) + (a.y_axis.y - q).powi(2) + (a.z_axis.z - q).powi(2) + 2.0 * p1;
+    let p = (p2 / 6.0).sqrt();
+    if p <= f32::EPSILON {
+        return None; // Voisinage isotrope : pas de direction privilégiée
+    }
+    let b = (a - Mat3::from_diagonal(Vec3::splat(q))) * (1.0 / p);
+    let phi = (b.determinant() / 2.0).clamp(-1.0, 1.0).acos() / 3.0;
+    let lambda = q + 2.0 * p * (phi + 2.0 * std::f32::consts::FRAC_PI_3).cos();
+
+    let m = a - Mat3::from_diagonal(Vec3::splat(lambda));
+    let rows = [m.row(0), m.row(1), m.row(2)];
+    [rows[0].cross(rows[1]), rows[0].cross(rows[2]), rows[1].cross(rows[2])]
+        .into_iter()
+        .max_by(|l, r| l.length_squared().total_cmp(&r.length_squared()))
+        .and_then(Vec3::try_normalize)
+}
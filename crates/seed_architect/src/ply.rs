@@ -0,0 +1,507 @@
+// crates/seed_architect/src/ply.rs
+//
+// Nuages de points PLY (ASCII, binaire little / big endian) :
+//   - import : x/y/z -> atomes, nx/ny/nz -> normales (PCA si absentes),
+//     red/green/blue -> attribut "color" (linéarisé), toute autre propriété scalaire du
+//     vertex -> attribut ATTR du même nom
+//   - export : .seed -> PLY binaire, pour inspection dans CloudCompare / MeshLab
+//     (positions absolues en double si le .seed porte une origine ORIG)
+// Un PLY avec des faces est un maillage : il part chez Assimp.
+// Un PLY de gaussiennes 3DGS (f_dc_*, scale_*, rot_*, opacity) part chez `splat`.
+
+use crate::assimp_importer::AssimpImporter;
+use crate::importer::{RawScene, COLOR_ATTRIBUTE};
+use crate::normals::point_normals;
+use crate::reader::{SeedError, SeedReader};
+use crate::splat::{is_gaussian, load_raw_splats};
+use crate::texture::{linear_to_srgb, srgb_to_linear};
+use dream_core::types::WorldPos;
+use glam::Vec3;
+use log::warn;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+/// Propriétés reconnues (les variantes PCL / MeshLab comprises)
+const NORMAL_NAMES: [[&str; 3]; 2] = [["nx", "ny", "nz"], ["normal_x", "normal_y", "normal_z"]];
+const COLOR_NAMES: [[&str; 3]; 2] = [["red", "green", "blue"], ["diffuse_red", "diffuse_green", "diffuse_blue"]];
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Encoding {
+    Ascii,
+    LittleEndian,
+    BigEndian,
+}
+
+#[derive(Clone, Copy, Debug)]
+enum Scalar {
+    I8,
+    U8,
+    I16,
+    U16,
+    I32,
+    U32,
+    F32,
+    F64,
+}
+
+impl Scalar {
+    fn parse(name: &str) -> Option<Self> {
+        Some(match name {
+            "char" | "int8" => Scalar::I8,
+            "uchar" | "uint8" => Scalar::U8,
+            "short" | "int16" => Scalar::I16,
+            "ushort" | "uint16" => Scalar::U16,
+            "int" | "int32" => Scalar::I32,
+            "uint" | "uint32" => Scalar::U32,
+            "float" | "float32" => Scalar::F32,
+            "double" | "float64" => Scalar::F64,
+            _ => return None,
+        })
+    }
+
+    fn size(self) -> usize {
+        match self {
+            Scalar::I8 | Scalar::U8 => 1,
+            Scalar::I16 | Scalar::U16 => 2,
+            Scalar::I32 | Scalar::U32 | Scalar::F32 => 4,
+            Scalar::F64 => 8,
+        }
+    }
+
+    /// Plage d'un canal de couleur entier (les flottants sont déjà en 0..1)
+    fn color_scale(self) -> f32 {
+        match self {
+            Scalar::U8 | Scalar::I8 => 255.0,
+            Scalar::U16 | Scalar::I16 => 65535.0,
+            _ => 1.0,
+        }
+    }
+
+    fn decode(self, bytes: &[u8], encoding: Encoding) -> f64 {
+        macro_rules! read {
+            ($t:ty) => {{
+                let raw: [u8; std::mem::size_of::<$t>()] = bytes.try_into().unwrap();
+                if encoding == Encoding::BigEndian { <$t>::from_be_bytes(raw) as f64 } else { <$t>::from_le_bytes(raw) as f64 }
+            }};
+        }
+        match self {
+            Scalar::I8 => read!(i8),
+            Scalar::U8 => read!(u8),
+            Scalar::I16 => read!(i16),
+            Scalar::U16 => read!(u16),
+            Scalar::I32 => read!(i32),
+            Scalar::U32 => read!(u32),
+            Scalar::F32 => read!(f32),
+            Scalar::F64 => read!(f64),
+        }
+    }
+}
+
+struct Property {
+    name: String,
+    kind: Scalar,
+    /// Type du compteur pour une propriété liste (`property list uchar int vertex_indices`)
+    list: Option<Scalar>,
+}
+
+struct Element {
+    name: String,
+    count: usize,
+    properties: Vec<Property>,
+}
+
+/// Sommets d'un fichier PLY
+pub struct PlyCloud {
+    pub positions: Vec<Vec3>,
+    pub normals: Option<Vec<Vec3>>,
+    /// Couleurs ramenées en 0..1
+    pub colors: Option<Vec<[f32; 3]>>,
+    /// Autres propriétés scalaires du vertex, dans l'ordre du header
+    pub scalars: Vec<(String, Vec<f32>)>,
+    /// Nombre de faces déclarées (0 pour un nuage de points)
+    pub faces: usize,
+}
+
+fn invalid(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+/// Lit l'élément `vertex` d'un PLY (les autres éléments sont parcourus puis ignorés)
+pub fn read_ply(path: impl AsRef<Path>) -> io::Result<PlyCloud> {
+    let bytes = std::fs::read(path)?;
+    let (encoding, elements, body) = parse_header(&bytes)?;
+
+    let mut ascii = std::str::from_utf8(if encoding == Encoding::Ascii { body } else { &[] })
+        .map_err(|e| invalid(format!("ASCII body is not UTF-8: {}", e)))?
+        .split_ascii_whitespace();
+    let mut cursor = 0usize;
+    let mut next = |kind: Scalar| -> io::Result<f64> {
+        if encoding == Encoding::Ascii {
+            let token = ascii.next().ok_or_else(|| invalid("unexpected end of ASCII body".into()))?;
+            return token.parse().map_err(|_| invalid(format!("bad ASCII value '{}'", token)));
+        }
+        let end = cursor + kind.size();
+        let raw = body.get(cursor..end).ok_or_else(|| invalid("unexpected end of binary body".into()))?;
+        cursor = end;
+        Ok(kind.decode(raw, encoding))
+    };
+
+    let mut columns: Vec<Vec<f32>> = Vec::new();
+    let mut vertex_properties: &[Property] = &[];
+    let mut faces = 0;
+    for element in &elements {
+        let is_vertex = element.name == "vertex";
+        if is_vertex {
+            vertex_properties = &element.properties;
+            columns = vec![Vec::with_capacity(element.count); element.properties.len()];
+        } else if element.name == "face" {
+            faces = element.count;
+        }
+        for _ in 0..element.count {
+            for (p, property) in element.properties.iter().enumerate() {
+                match property.list {
+                    Some(count_kind) => {
+                        // Listes (indices de face...) : lues puis ignorées
+                        for _ in 0..next(count_kind)? as usize {
+                            next(property.kind)?;
+                        }
+                    }
+                    None => {
+                        let value = next(property.kind)?;
+                        if is_vertex {
+                            columns[p].push(value as f32);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    let find = |name: &str| vertex_properties.iter().position(|p| p.name == name && p.list.is_none());
+    let triple = |names: &[[&str; 3]]| {
+        names.iter().find_map(|n| Some([find(n[0])?, find(n[1])?, find(n[2])?]))
+    };
+    let Some(xyz) = triple(&[["x", "y", "z"]]) else {
+        return Err(invalid("vertex element has no x/y/z".into()));
+    };
+    let normal_columns = triple(&NORMAL_NAMES);
+    let color_columns = triple(&COLOR_NAMES);
+
+    let count = columns.first().map_or(0, Vec::len);
+    let vec3 = |c: [usize; 3], i: usize| Vec3::new(columns[c[0]][i], columns[c[1]][i], columns[c[2]][i]);
+    let cloud = PlyCloud {
+        positions: (0..count).map(|i| vec3(xyz, i)).collect(),
+        normals: normal_columns.map(|c| (0..count).map(|i| vec3(c, i).normalize_or_zero()).collect()),
+        colors: color_columns.map(|c| {
+            let scale = vertex_properties[c[0]].kind.color_scale();
+            (0..count).map(|i| (vec3(c, i) / scale).to_array()).collect()
+        }),
+        scalars: vertex_properties.iter().enumerate()
+            .filter(|(p, property)| {
+                let used = [Some(xyz), normal_columns, color_columns].iter().flatten().any(|c| c.contains(p));
+                property.list.is_none() && !used
+            })
+            .map(|(p, property)| (property.name.clone(), std::mem::take(&mut columns[p])))
+            .collect(),
+        faces,
+    };
+    Ok(cloud)
+}
+
+/// Header PLY : encodage, éléments, et octets du corps
+fn parse_header(bytes: &[u8]) -> io::Result<(Encoding, Vec<Element>, &[u8])> {
+    const END: &[u8] = b"end_header";
+    let end = bytes.windows(END.len()).position(|w| w == END)
+        .ok_or_else(|| invalid("missing end_header".into()))?;
+    // Le corps commence après la fin de ligne de `end_header` (\n ou \r\n)
+    let body_start = bytes[end..].iter().position(|&b| b == b'\n').map_or(bytes.len(), |n| end + n + 1);
+    let header = std::str::from_utf8(&bytes[..end]).map_err(|_| invalid("header is not UTF-8".into()))?;
+
+    let mut lines = header.lines();
+    if lines.next().map(str::trim) != Some("ply") {
+        return Err(invalid("not a PLY file".into()));
+    }
+    let mut encoding = None;
+    let mut elements: Vec<Element> = Vec::new();
+    for line in lines {
+        let words: Vec<&str> = line.split_whitespace().collect();
+        match words.as_slice() {
+            ["format", format, ..] => {
+                encoding = Some(match *format {
+                    "ascii" => Encoding::Ascii,
+                    "binary_little_endian" => Encoding::LittleEndian,
+                    "binary_big_endian" => Encoding::BigEndian,
+                    other => return Err(invalid(format!("unknown format '{}'", other))),
+                });
+            }
+            ["element", name, count] => elements.push(Element {
+                name: name.to_string(),
+                count: count.parse().map_err(|_| invalid(format!("bad element count '{}'", count)))?,
+                properties: Vec::new(),
+            }),
+            ["property", "list", count, kind, name] => {
+                let property = Property {
+                    name: name.to_string(),
+                    kind: Scalar::parse(kind).ok_or_else(|| invalid(format!("unknown type '{}'", kind)))?,
+                    list: Some(Scalar::parse(count).ok_or_else(|| invalid(format!("unknown type '{}'", count)))?),
+                };
+                elements.last_mut().ok_or_else(|| invalid("property before element".into()))?.properties.push(property);
+            }
+            ["property", kind, name] => {
+                let property = Property {
+                    name: name.to_string(),
+                    kind: Scalar::parse(kind).ok_or_else(|| invalid(format!("unknown type '{}'", kind)))?,
+                    list: None,
+                };
+                elements.last_mut().ok_or_else(|| invalid("property before element".into()))?.properties.push(property);
+            }
+            _ => {} // comment, obj_info
+        }
+    }
+    let encoding = encoding.ok_or_else(|| invalid("missing format line".into()))?;
+    Ok((encoding, elements, &bytes[body_start..]))
+}
+
+pub struct PlyImporter;
+
+impl PlyImporter {
+    pub fn handles(path: &str) -> bool {
+        Path::new(path).extension()
+            .and_then(|e| e.to_str())
+            .is_some_and(|e| e.eq_ignore_ascii_case("ply"))
+    }
+
+    /// Nuage de points -> un objet "points" ; PLY avec faces -> Assimp ; 3DGS -> gaussiennes
+    pub(crate) fn load_raw_ply(path: &str, crease_angle: f32) -> RawScene {
+        let cloud = read_ply(path).unwrap_or_else(|e| panic!("❌ Erreur Ingestion PLY {} : {}", path, e));
+        if cloud.faces > 0 {
+            return AssimpImporter::load_raw_assimp(path, crease_angle);
+        }
+        if is_gaussian(&cloud) {
+            return load_raw_splats(cloud);
+        }
+
+        let normals = cloud.normals.unwrap_or_else(|| point_normals(&cloud.positions));
+        let mut raw = RawScene {
+            points: cloud.positions.iter().zip(&normals)
+                .map(|(p, n)| {
+                    let n = if *n == Vec3::ZERO { Vec3::Y } else { *n };
+                    [p.x, p.y, p.z, n.x, n.y, n.z]
+                })
+                .collect(),
+            objects: vec![("points".to_string(), 0)],
+            ..Default::default()
+        };
+        if let Some(colors) = cloud.colors {
+            // Couleurs PLY en sRGB : "color" est linéaire
+            let data = colors.iter().flatten().map(|&c| srgb_to_linear(c)).collect();
+            raw.point_attributes.push((COLOR_ATTRIBUTE.to_string(), 3, data));
+        }
+        for (name, values) in cloud.scalars {
+            if name.len() > 32 {
+                warn!("⚠️ Propriété PLY `{}` tronquée à 32 octets", name);
+            }
+            raw.point_attributes.push((name, 1, values));
+        }
+        raw
+    }
+}
+
+/// Exporte un .seed en PLY binaire : positions, normales, `color` en uchar sRGB,
+/// et chaque attribut en propriété float (`nom_0`, `nom_1`... s'il a plusieurs composantes).
+/// Avec un chunk ORIG, les positions sont remises en coordonnées absolues, en double :
+/// un f32 ne garde pas le millimètre à des millions de mètres de l'origine.
+pub fn export_ply(seed: &SeedReader, output_path: impl AsRef<Path>) -> Result<(), SeedError> {
+    let atoms = seed.geometry()?;
+    let origin = seed.origin()?;
+    let attributes = seed.attributes()?;
+    let color = attributes.iter()
+        .find(|(header, data)| header.name() == COLOR_ATTRIBUTE && header.components == 3 && data.len() == atoms.len() * 3);
+    let others: Vec<_> = attributes.iter()
+        .filter(|(header, data)| header.name() != COLOR_ATTRIBUTE && data.len() == atoms.len() * header.components as usize)
+        .collect();
+
+    let mut out = BufWriter::new(File::create(output_path)?);
+    writeln!(out, "ply\nformat binary_little_endian 1.0")?;
+    writeln!(out, "comment seed_architect {}", env!("CARGO_PKG_VERSION"))?;
+    writeln!(out, "element vertex {}", atoms.len())?;
+    let position_type = if origin.is_some() { "double" } else { "float" };
+    for name in ["x", "y", "z"] {
+        writeln!(out, "property {} {}", position_type, name)?;
+    }
+    for name in ["nx", "ny", "nz"] {
+        writeln!(out, "property float {}", name)?;
+    }
+    if color.is_some() {
+        writeln!(out, "property uchar red\nproperty uchar green\nproperty uchar blue")?;
+    }
+    for (header, _) in &others {
+        // Espaces interdits dans un nom de propriété PLY
+        let name = header.name().replace(char::is_whitespace, "_");
+        match header.components {
+            1 => writeln!(out, "property float {}", name)?,
+            n => (0..n).try_for_each(|k| writeln!(out, "property float {}_{}", name, k))?,
+        }
+    }
+    writeln!(out, "end_header")?;
+
+    for (i, atom) in atoms.iter().enumerate() {
+        match origin {
+            Some(origin) => {
+                let position = origin + WorldPos::new(atom[0] as f64, atom[1] as f64, atom[2] as f64);
+                for value in position.to_array() {
+                    out.write_all(&value.to_le_bytes())?;
+                }
+            }
+            None => atom[..3].iter().try_for_each(|value| out.write_all(&value.to_le_bytes()))?,
+        }
+        for value in &atom[3..] {
+            out.write_all(&value.to_le_bytes())?;
+        }
+        if let Some((_, data)) = color {
+            let rgb = [0, 1, 2].map(|c| (linear_to_srgb(data[i * 3 + c].clamp(0.0, 1.0)) * 255.0).round() as u8);
+            out.write_all(&rgb)?;
+        }
+        for (header, data) in &others {
+            let n = header.components as usize;
+            for value in &data[i * n..(i + 1) * n] {
+                out.write_all(&value.to_le_bytes())?;
+            }
+        }
+    }
+    out.flush()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::format::{ChunkKind, SeedWriter, WorldOrigin};
+
+    const HEADER: &str = "comment test\n\
+        element vertex 3\n\
+        property double x\nproperty float y\nproperty float z\n\
+        property float nx\nproperty float ny\nproperty float nz\n\
+        property uchar red\nproperty uchar green\nproperty uchar blue\n\
+        property ushort intensity\n\
+        element face 1\n\
+        property list uchar int vertex_indices\n\
+        end_header\n";
+
+    /// Position, normale (non normalisée), couleur, intensité
+    type Vertex = ([f64; 3], [f32; 3], [u8; 3], u16);
+
+    const VERTICES: [Vertex; 3] = [
+        ([1.5, -2.0, 3.25], [0.0, 0.0, 2.0], [255, 0, 51], 7),
+        ([0.0, 0.5, -1.0], [0.0, 3.0, 0.0], [0, 255, 102], 300),
+        ([-4.0, 8.0, 0.125], [1.0, 0.0, 0.0], [128, 128, 128], 65535),
+    ];
+
+    fn temp_path(name: &str) -> std::path::PathBuf {
+        std::env::temp_dir().join(format!("seed_architect_{}_{}.ply", name, std::process::id()))
+    }
+
+    fn read(name: &str, bytes: &[u8]) -> io::Result<PlyCloud> {
+        let path = temp_path(name);
+        std::fs::write(&path, bytes).unwrap();
+        let cloud = read_ply(&path);
+        std::fs::remove_file(&path).unwrap();
+        cloud
+    }
+
+    fn ascii() -> Vec<u8> {
+        let mut text = format!("ply\nformat ascii 1.0\n{}", HEADER);
+        for (p, n, c, i) in VERTICES {
+            text += &format!("{} {} {} {} {} {} {} {} {} {}\n", p[0], p[1], p[2], n[0], n[1], n[2], c[0], c[1], c[2], i);
+        }
+        text += "3 0 1 2\n";
+        text.into_bytes()
+    }
+
+    fn binary(big_endian: bool) -> Vec<u8> {
+        let format = if big_endian { "binary_big_endian" } else { "binary_little_endian" };
+        let mut bytes = format!("ply\nformat {} 1.0\n{}", format, HEADER).into_bytes();
+        macro_rules! put {
+            ($v:expr) => {
+                bytes.extend_from_slice(&if big_endian { $v.to_be_bytes() } else { $v.to_le_bytes() })
+            };
+        }
+        for (p, n, c, i) in VERTICES {
+            put!(p[0]);
+            put!(p[1] as f32);
+            put!(p[2] as f32);
+            n.iter().for_each(|v| put!(*v));
+            bytes.extend_from_slice(&c);
+            put!(i);
+        }
+        bytes.push(3);
+        [0i32, 1, 2].iter().for_each(|v| put!(*v));
+        bytes
+    }
+
+    #[test]
+    fn reads_every_encoding() {
+        for (name, bytes) in [("ascii", ascii()), ("le", binary(false)), ("be", binary(true))] {
+            let cloud = read(name, &bytes).unwrap();
+            assert_eq!(cloud.faces, 1, "{}", name);
+            assert_eq!(cloud.positions.len(), 3, "{}", name);
+            let normals = cloud.normals.as_ref().unwrap();
+            let colors = cloud.colors.as_ref().unwrap();
+            for (k, (p, n, c, i)) in VERTICES.iter().enumerate() {
+                assert_eq!(cloud.positions[k], Vec3::new(p[0] as f32, p[1] as f32, p[2] as f32), "{}", name);
+                assert_eq!(normals[k], Vec3::from(*n).normalize(), "{}", name);
+                assert_eq!(colors[k], c.map(|c| c as f32 / 255.0), "{}", name);
+                assert_eq!(cloud.scalars[0].1[k], *i as f32, "{}", name);
+            }
+            assert_eq!(cloud.scalars.len(), 1);
+            assert_eq!(cloud.scalars[0].0, "intensity");
+        }
+    }
+
+    #[test]
+    fn rejects_malformed_files() {
+        assert!(read("no_ply", b"plx\nformat ascii 1.0\nend_header\n").is_err());
+        assert!(read("no_end", b"ply\nformat ascii 1.0\nelement vertex 1\n").is_err());
+        assert!(read("no_xyz", b"ply\nformat ascii 1.0\nelement vertex 1\nproperty float x\nend_header\n1\n").is_err());
+        let truncated = binary(false);
+        assert!(read("truncated", &truncated[..truncated.len() - 20]).is_err());
+    }
+
+    #[test]
+    fn export_restores_origin_and_srgb() {
+        let origin = WorldPos::new(650_000.0, 6_860_000.0, 100.0);
+        let atoms = [[0.25f32, -0.5, 1.0, 0.0, 0.0, 1.0], [-1.0, 2.0, 0.0, 1.0, 0.0, 0.0]];
+        let linear = [srgb_to_linear(0.2), 1.0, 0.0, 0.5, srgb_to_linear(0.6), 0.25];
+        let mut writer = SeedWriter::new(atoms.len() as u64);
+        writer
+            .add_pod_chunk(ChunkKind::Geometry, &atoms)
+            .add_pod_chunk(ChunkKind::Origin, &[WorldOrigin::new(origin)])
+            .add_attribute(COLOR_ATTRIBUTE, 3, &linear);
+        let mut bytes = Vec::new();
+        writer.write(&mut bytes).unwrap();
+        let seed = SeedReader::from_bytes(&bytes).unwrap();
+
+        let path = temp_path("export");
+        export_ply(&seed, &path).unwrap();
+        let exported = std::fs::read(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        let (encoding, elements, body) = parse_header(&exported).unwrap();
+        assert_eq!(encoding, Encoding::LittleEndian);
+        assert!(matches!(elements[0].properties[0].kind, Scalar::F64));
+        // x y z en double, 3 normales float, 3 uchar
+        let stride = 3 * 8 + 3 * 4 + 3;
+        for (k, atom) in atoms.iter().enumerate() {
+            let vertex = &body[k * stride..(k + 1) * stride];
+            for c in 0..3 {
+                let value = f64::from_le_bytes(vertex[c * 8..c * 8 + 8].try_into().unwrap());
+                assert_eq!(value, origin[c] + atom[c] as f64);
+            }
+            let rgb = &vertex[36..39];
+            let expected = [0, 1, 2].map(|c| (linear_to_srgb(linear[k * 3 + c]) * 255.0).round() as u8);
+            assert_eq!(rgb, expected);
+        }
+        assert_eq!(body.len(), atoms.len() * stride);
+    }
+}
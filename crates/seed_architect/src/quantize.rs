@@ -0,0 +1,138 @@
+// crates/seed_architect/src/quantize.rs
+//
+// Layout d'atome compact (12 octets au lieu de 24) :
+//   - position : 3 × u16 relatifs à l'AABB du chunk
+//   - normale  : octaédrique, 2 × snorm16 (unpackSnorm2x16 côté GLSL)
+//
+// Le chunk GEOM commence alors par un `QuantizedBounds` (32 octets) suivi des atomes.
+
+use bytemuck::{Pod, Zeroable};
+use glam::{Vec2, Vec3};
+
+/// Encodage des atomes dans le chunk GEOM (enregistré dans le header)
+#[repr(u32)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum AtomLayout {
+    /// `[f32; 6]` : position + normale en clair
+    #[default]
+    Full = 0,
+    /// `QuantizedBounds` + `QuantizedAtom`
+    Quantized = 1,
+}
+
+impl AtomLayout {
+    pub fn from_u32(value: u32) -> Option<Self> {
+        match value {
+            0 => Some(AtomLayout::Full),
+            1 => Some(AtomLayout::Quantized),
+            _ => None,
+        }
+    }
+
+    /// Taille d'un atome en octets
+    pub fn stride(self) -> u32 {
+        match self {
+            AtomLayout::Full => std::mem::size_of::<[f32; 6]>() as u32,
+            AtomLayout::Quantized => std::mem::size_of::<QuantizedAtom>() as u32,
+        }
+    }
+
+    /// Octets avant le premier atome dans le chunk GEOM
+    pub fn preamble_size(self) -> u64 {
+        match self {
+            AtomLayout::Full => 0,
+            AtomLayout::Quantized => std::mem::size_of::<QuantizedBounds>() as u64,
+        }
+    }
+}
+
+/// AABB de quantification (vec4 pour coller à l'alignement std430)
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, Pod, Zeroable)]
+pub struct QuantizedBounds {
+    pub min: [f32; 4],
+    pub max: [f32; 4],
+}
+
+/// Atome compact : `xy` = x | y << 16, `z` = z (16 bits hauts libres), `normal` = octaédrique
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, Pod, Zeroable)]
+pub struct QuantizedAtom {
+    pub xy: u32,
+    pub z: u32,
+    pub normal: u32,
+}
+
+/// Normale unitaire -> octaèdre déplié dans [-1, 1]²
+pub fn oct_encode(n: Vec3) -> u32 {
+    let n = n / (n.x.abs() + n.y.abs() + n.z.abs()).max(f32::EPSILON);
+    let mut e = Vec2::new(n.x, n.y);
+    if n.z < 0.0 {
+        e = (Vec2::ONE - Vec2::new(e.y.abs(), e.x.abs())) * Vec2::new(sign(e.x), sign(e.y));
+    }
+    pack_snorm16(e.x) | (pack_snorm16(e.y) << 16)
+}
+
+/// Inverse de `oct_encode` (même formule que `octDecode` dans les shaders)
+pub fn oct_decode(packed: u32) -> Vec3 {
+    let e = Vec2::new(unpack_snorm16(packed & 0xFFFF), unpack_snorm16(packed >> 16));
+    let mut n = Vec3::new(e.x, e.y, 1.0 - e.x.abs() - e.y.abs());
+    let t = (-n.z).max(0.0);
+    n.x += if n.x >= 0.0 { -t } else { t };
+    n.y += if n.y >= 0.0 { -t } else { t };
+    n.normalize_or_zero()
+}
+
+fn sign(v: f32) -> f32 {
+    if v >= 0.0 { 1.0 } else { -1.0 }
+}
+
+fn pack_snorm16(v: f32) -> u32 {
+    ((v.clamp(-1.0, 1.0) * 32767.0).round() as i16) as u16 as u32
+}
+
+fn unpack_snorm16(bits: u32) -> f32 {
+    ((bits as u16 as i16) as f32 / 32767.0).max(-1.0)
+}
+
+/// Quantifie des atomes `[f32; 6]` dans leur AABB
+pub fn quantize_atoms(atoms: &[[f32; 6]]) -> (QuantizedBounds, Vec<QuantizedAtom>) {
+    let (min, max) = atoms.iter().fold(
+        (Vec3::splat(f32::MAX), Vec3::splat(f32::MIN)),
+        |(lo, hi), a| {
+            let p = Vec3::new(a[0], a[1], a[2]);
+            (lo.min(p), hi.max(p))
+        },
+    );
+    let (min, max) = if atoms.is_empty() { (Vec3::ZERO, Vec3::ZERO) } else { (min, max) };
+    let extent = max - min;
+    let scale = Vec3::select(extent.cmpgt(Vec3::ZERO), 65535.0 / extent, Vec3::ZERO);
+
+    let quantized = atoms.iter().map(|a| {
+        let q = ((Vec3::new(a[0], a[1], a[2]) - min) * scale).round();
+        QuantizedAtom {
+            xy: q.x as u32 | ((q.y as u32) << 16),
+            z: q.z as u32,
+            normal: oct_encode(Vec3::new(a[3], a[4], a[5])),
+        }
+    }).collect();
+
+    let bounds = QuantizedBounds {
+        min: min.extend(0.0).to_array(),
+        max: max.extend(0.0).to_array(),
+    };
+    (bounds, quantized)
+}
+
+/// Reconstruit les atomes `[f32; 6]` (précision : extent / 65535 par axe)
+pub fn dequantize_atoms(bounds: &QuantizedBounds, atoms: &[QuantizedAtom]) -> Vec<[f32; 6]> {
+    let min = Vec3::from_slice(&bounds.min[..3]);
+    let extent = Vec3::from_slice(&bounds.max[..3]) - min;
+
+    atoms.iter().map(|a| {
+        let q = Vec3::new((a.xy & 0xFFFF) as f32, (a.xy >> 16) as f32, (a.z & 0xFFFF) as f32);
+        let p = min + q / 65535.0 * extent;
+        let n = oct_decode(a.normal);
+        [p.x, p.y, p.z, n.x, n.y, n.z]
+    }).collect()
+}
@@ -0,0 +1,562 @@
+// crates/seed_architect/src/reader.rs
+//
+// Chargeur unique des fichiers .SEED : tous les consommateurs (runtime, outils)
+// passent par ici au lieu de parser le header à la main.
+//
+// Le fichier est mappé en mémoire (zero-copy) : seules les pages réellement
+// lues (header, table, chunks consultés) sont chargées par l'OS.
+// Les chunks compressés (zstd) sont décodés en flux, à la demande.
+
+use bytemuck::{Pod, Zeroable};
+use std::fmt;
+use memmap2::Mmap;
+use std::borrow::Cow;
+use std::fs::File;
+use std::io::{self, Read};
+use std::path::Path;
+
+use crate::bvh::BvhNode;
+use dream_core::types::WorldPos;
+use crate::format::{
+    checksum, AttributeHeader, ChunkEntry, ChunkKind, OrderingInfo, SeedFileHeader, SeedMetadata, SeedObject, WorldOrigin,
+    ArchivedSeedMetadata, CHUNK_ALIGNMENT, SEED_FLAG_SPLATS, SEED_MAGIC, SEED_VERSION,
+};
+use crate::importer::MaterialData;
+use crate::quantize::{dequantize_atoms, AtomLayout, QuantizedAtom, QuantizedBounds};
+
+/// Erreur de lecture d'un fichier .SEED
+#[derive(Debug)]
+pub enum SeedError {
+    IoError(io::Error),
+    /// Le fichier est plus court que ce qu'annoncent le header ou la table
+    Truncated { expected: u64, actual: u64 },
+    BadMagic([u8; 4]),
+    UnsupportedVersion(u32),
+    UnsupportedLayout(u32),
+    /// Un payload ne respecte pas `CHUNK_ALIGNMENT`
+    Misaligned { kind: u32, offset: u64 },
+    /// La taille d'un chunk ne correspond pas à son contenu déclaré
+    SizeMismatch { kind: u32, expected: u64, actual: u64 },
+    ChecksumMismatch { kind: u32 },
+    MissingChunk(ChunkKind),
+    /// L'archive rkyv d'un chunk ne passe pas la validation
+    InvalidArchive { kind: u32 },
+}
+
+fn fourcc(kind: u32) -> String {
+    String::from_utf8_lossy(&kind.to_le_bytes()).into_owned()
+}
+
+impl fmt::Display for SeedError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SeedError::IoError(e) => write!(f, "IO Error: {}", e),
+            SeedError::Truncated { expected, actual } => {
+                write!(f, "Truncated file: expected at least {} bytes, got {}", expected, actual)
+            }
+            SeedError::BadMagic(magic) => write!(f, "Wrong magic: {:?} (expected \"SEED\")", magic),
+            SeedError::UnsupportedVersion(v) => {
+                write!(f, "Unsupported version: {} (expected {})", v, SEED_VERSION)
+            }
+            SeedError::UnsupportedLayout(layout) => write!(f, "Unsupported atom layout: {}", layout),
+            SeedError::Misaligned { kind, offset } => {
+                write!(f, "Chunk '{}' misaligned at offset {}", fourcc(*kind), offset)
+            }
+            SeedError::SizeMismatch { kind, expected, actual } => write!(
+                f,
+                "Chunk '{}' size mismatch: expected {} bytes, got {}",
+                fourcc(*kind), expected, actual
+            ),
+            SeedError::ChecksumMismatch { kind } => {
+                write!(f, "Chunk '{}' checksum mismatch", fourcc(*kind))
+            }
+            SeedError::MissingChunk(kind) => write!(f, "Missing chunk '{}'", fourcc(*kind as u32)),
+            SeedError::InvalidArchive { kind } => {
+                write!(f, "Chunk '{}' holds an invalid archive", fourcc(*kind))
+            }
+        }
+    }
+}
+
+impl std::error::Error for SeedError {}
+
+impl From<io::Error> for SeedError {
+    fn from(err: io::Error) -> Self {
+        SeedError::IoError(err)
+    }
+}
+
+/// Palier de croissance du tampon d'un chunk compressé (octets)
+const DECODE_STEP: usize = 16 * 1024 * 1024;
+
+/// Bloc aligné sur 16 octets : garantit que les casts `bytemuck` des payloads
+/// (f32, u64...) tombent toujours sur une adresse valide.
+#[repr(C, align(16))]
+#[derive(Clone, Copy, Pod, Zeroable)]
+struct AlignedBlock([u8; 16]);
+
+/// Octets du fichier : mappés (cas normal) ou copiés (`from_bytes`)
+enum SeedStorage {
+    Mapped(Mmap),
+    Owned(Vec<AlignedBlock>, usize),
+}
+
+impl SeedStorage {
+    fn bytes(&self) -> &[u8] {
+        match self {
+            SeedStorage::Mapped(map) => map,
+            SeedStorage::Owned(blocks, len) => &bytemuck::cast_slice::<AlignedBlock, u8>(blocks)[..*len],
+        }
+    }
+}
+
+/// Flux d'attributs décodé : en-tête + `count * components` valeurs
+pub type AttributeStream<'a> = (AttributeHeader, Cow<'a, [f32]>);
+
+/// Fichier .SEED ouvert et validé
+pub struct SeedReader {
+    storage: SeedStorage,
+    header: SeedFileHeader,
+    chunks: Vec<ChunkEntry>,
+}
+
+impl SeedReader {
+    /// Mappe et valide un fichier .SEED (magic, version, bornes, alignement).
+    /// Les checksums ne sont pas recalculés ici pour ne pas toucher toutes les
+    /// pages : voir `verify_checksums`.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, SeedError> {
+        let file = File::open(path)?;
+        // SAFETY: les bakes ne réécrivent jamais un .seed en place : ils écrivent
+        // `<out>.tmp` puis le renomment (`write_atomically`), ce mapping garde
+        // l'ancien inode intact jusqu'à sa fermeture.
+        let map = unsafe { Mmap::map(&file)? };
+        Self::from_storage(SeedStorage::Mapped(map))
+    }
+
+    /// Valide un .SEED déjà en mémoire
+    pub fn from_bytes(data: &[u8]) -> Result<Self, SeedError> {
+        let mut blocks = vec![AlignedBlock([0; 16]); data.len().div_ceil(16)];
+        bytemuck::cast_slice_mut::<AlignedBlock, u8>(blocks.as_mut_slice())[..data.len()]
+            .copy_from_slice(data);
+        Self::from_storage(SeedStorage::Owned(blocks, data.len()))
+    }
+
+    fn from_storage(storage: SeedStorage) -> Result<Self, SeedError> {
+        let bytes = storage.bytes();
+        let len = bytes.len();
+        let file_len = len as u64;
+
+        // 1. Header
+        let header_size = std::mem::size_of::<SeedFileHeader>();
+        if len < header_size {
+            return Err(SeedError::Truncated { expected: header_size as u64, actual: file_len });
+        }
+        let header: SeedFileHeader = bytemuck::pod_read_unaligned(&bytes[..header_size]);
+        if header.magic != SEED_MAGIC {
+            return Err(SeedError::BadMagic(header.magic));
+        }
+        if header.version != SEED_VERSION {
+            return Err(SeedError::UnsupportedVersion(header.version));
+        }
+
+        // 2. Table des chunks
+        let entry_size = std::mem::size_of::<ChunkEntry>() as u64;
+        let toc_end = header.toc_offset.saturating_add(header.chunk_count as u64 * entry_size);
+        if toc_end > file_len {
+            return Err(SeedError::Truncated { expected: toc_end, actual: file_len });
+        }
+        let chunks: Vec<ChunkEntry> = bytes[header.toc_offset as usize..toc_end as usize]
+            .chunks_exact(entry_size as usize)
+            .map(bytemuck::pod_read_unaligned)
+            .collect();
+
+        // 3. Chaque payload : bornes et alignement
+        for entry in &chunks {
+            if !entry.is_compressed() && entry.raw_size != entry.size {
+                return Err(SeedError::SizeMismatch { kind: entry.kind, expected: entry.size, actual: entry.raw_size });
+            }
+            let end = entry.offset.saturating_add(entry.size);
+            if end > file_len {
+                return Err(SeedError::Truncated { expected: end, actual: file_len });
+            }
+            if entry.offset % CHUNK_ALIGNMENT != 0 {
+                return Err(SeedError::Misaligned { kind: entry.kind, offset: entry.offset });
+            }
+        }
+
+        let layout = AtomLayout::from_u32(header.atom_layout)
+            .ok_or(SeedError::UnsupportedLayout(header.atom_layout))?;
+        let reader = Self { storage, header, chunks };
+
+        // 4. Cohérence des chunks connus
+        if let Some(geo) = reader.chunk(ChunkKind::Geometry) {
+            let expected = header.vertex_count
+                .saturating_mul(layout.stride() as u64)
+                .saturating_add(layout.preamble_size());
+            if geo.raw_size != expected {
+                return Err(SeedError::SizeMismatch { kind: geo.kind, expected, actual: geo.raw_size });
+            }
+        }
+        let records = [
+            (ChunkKind::Materials, std::mem::size_of::<MaterialData>()),
+            (ChunkKind::Bvh, std::mem::size_of::<BvhNode>()),
+            (ChunkKind::Objects, std::mem::size_of::<SeedObject>()),
+            (ChunkKind::Origin, std::mem::size_of::<WorldOrigin>()),
+        ];
+        for (kind, record_size) in records {
+            if let Some(entry) = reader.chunk(kind) {
+                let expected = entry.count.saturating_mul(record_size as u64);
+                if entry.raw_size != expected {
+                    return Err(SeedError::SizeMismatch { kind: entry.kind, expected, actual: entry.raw_size });
+                }
+            }
+        }
+
+        Ok(reader)
+    }
+
+    /// Recalcule le CRC32 de chaque chunk (lit tout le fichier)
+    pub fn verify_checksums(&self) -> Result<(), SeedError> {
+        for entry in &self.chunks {
+            if checksum(self.chunk_bytes(entry)) != entry.checksum {
+                return Err(SeedError::ChecksumMismatch { kind: entry.kind });
+            }
+        }
+        Ok(())
+    }
+
+    fn bytes(&self) -> &[u8] {
+        self.storage.bytes()
+    }
+
+    pub fn header(&self) -> &SeedFileHeader {
+        &self.header
+    }
+
+    pub fn vertex_count(&self) -> u64 {
+        self.header.vertex_count
+    }
+
+    /// Gaussiennes 3DGS (SEED_FLAG_SPLATS) : attributs `splat_*`, rendu trié
+    pub fn is_splats(&self) -> bool {
+        self.header.flags & SEED_FLAG_SPLATS != 0
+    }
+
+    /// Empreinte des sources et réglages du bake (zéros si inconnue)
+    pub fn content_hash(&self) -> [u8; 32] {
+        self.header.content_hash
+    }
+
+    /// Table des chunks, y compris ceux de types inconnus
+    pub fn chunks(&self) -> &[ChunkEntry] {
+        &self.chunks
+    }
+
+    /// Premier chunk du type demandé
+    pub fn chunk(&self, kind: ChunkKind) -> Option<&ChunkEntry> {
+        self.chunks.iter().find(|e| e.kind() == Some(kind))
+    }
+
+    /// Octets stockés d'un chunk (bornés à l'ouverture), directement dans le mmap.
+    /// Pour un chunk compressé, ce sont les octets zstd.
+    pub fn chunk_bytes(&self, entry: &ChunkEntry) -> &[u8] {
+        &self.bytes()[entry.offset as usize..(entry.offset + entry.size) as usize]
+    }
+
+    /// Taille totale des payloads : (stockée, décodée)
+    pub fn payload_sizes(&self) -> (u64, u64) {
+        self.chunks.iter().fold((0, 0), |(stored, raw), e| (stored + e.size, raw + e.raw_size))
+    }
+
+    /// Flux de lecture décodé d'un chunk (zstd en streaming si compressé)
+    pub fn chunk_stream(&self, entry: &ChunkEntry) -> Result<Box<dyn Read + '_>, SeedError> {
+        let bytes = self.chunk_bytes(entry);
+        if entry.is_compressed() {
+            Ok(Box::new(zstd::stream::read::Decoder::with_buffer(bytes)?.take(entry.raw_size)))
+        } else {
+            Ok(Box::new(bytes))
+        }
+    }
+
+    /// Parcourt le contenu décodé d'un chunk par tranches d'au plus `slice_size` octets.
+    /// Un chunk brut est servi directement depuis le mmap, sans copie.
+    /// `f` reçoit l'offset décodé de la tranche et ses octets.
+    pub fn stream_chunk(
+        &self,
+        entry: &ChunkEntry,
+        slice_size: usize,
+        mut f: impl FnMut(u64, &[u8]),
+    ) -> Result<(), SeedError> {
+        let slice_size = slice_size.max(1);
+        if !entry.is_compressed() {
+            for (i, piece) in self.chunk_bytes(entry).chunks(slice_size).enumerate() {
+                f((i * slice_size) as u64, piece);
+            }
+            return Ok(());
+        }
+
+        let mut stream = self.chunk_stream(entry)?;
+        let mut buffer = vec![0u8; slice_size.min(entry.raw_size as usize)];
+        let mut offset = 0u64;
+        while offset < entry.raw_size {
+            let len = (entry.raw_size - offset).min(slice_size as u64) as usize;
+            stream.read_exact(&mut buffer[..len])?;
+            f(offset, &buffer[..len]);
+            offset += len as u64;
+        }
+        Ok(())
+    }
+
+    /// Contenu décodé d'un chunk vu comme une slice `T` :
+    /// emprunté au mmap si brut, décompressé dans un Vec sinon.
+    fn decode_pod<T: Pod>(&self, entry: &ChunkEntry) -> Result<Cow<'_, [T]>, SeedError> {
+        if !entry.is_compressed() {
+            return bytemuck::try_cast_slice(self.chunk_bytes(entry))
+                .map(Cow::Borrowed)
+                .map_err(|_| SeedError::Misaligned { kind: entry.kind, offset: entry.offset });
+        }
+        // `raw_size` vient du fichier : le tampon grandit par paliers au rythme des
+        // octets réellement décompressés, un en-tête mensonger ne réserve rien d'avance
+        let item = std::mem::size_of::<T>().max(1);
+        let count = entry.raw_size as usize / item;
+        let mut stream = self.chunk_stream(entry)?;
+        let mut data: Vec<T> = Vec::new();
+        while data.len() < count {
+            let start = data.len();
+            let step = (count - start).min((DECODE_STEP / item).max(1));
+            data.resize(start + step, T::zeroed());
+            let bytes: &mut [u8] = bytemuck::cast_slice_mut(&mut data[start..]);
+            let mut filled = 0;
+            while filled < bytes.len() {
+                match stream.read(&mut bytes[filled..])? {
+                    0 => {
+                        let actual = (start * item + filled) as u64;
+                        return Err(SeedError::SizeMismatch { kind: entry.kind, expected: entry.raw_size, actual });
+                    }
+                    n => filled += n,
+                }
+            }
+        }
+        Ok(Cow::Owned(data))
+    }
+
+    fn pod_chunk<T: Pod>(&self, kind: ChunkKind) -> Result<Cow<'_, [T]>, SeedError> {
+        match self.chunk(kind) {
+            Some(entry) => self.decode_pod(entry),
+            None => Ok(Cow::Borrowed(&[])),
+        }
+    }
+
+    /// Encodage des atomes (validé à l'ouverture)
+    pub fn atom_layout(&self) -> AtomLayout {
+        AtomLayout::from_u32(self.header.atom_layout).unwrap_or_default()
+    }
+
+    /// Atomes (Positions + Normales), déquantifiés si besoin
+    pub fn geometry(&self) -> Result<Cow<'_, [[f32; 6]]>, SeedError> {
+        let entry = self.geometry_chunk()?;
+        match self.atom_layout() {
+            AtomLayout::Full => self.decode_pod(entry),
+            AtomLayout::Quantized => {
+                let words = self.decode_pod::<u32>(entry)?;
+                let split = AtomLayout::Quantized.preamble_size() as usize / 4;
+                let bounds: QuantizedBounds = bytemuck::pod_read_unaligned(bytemuck::cast_slice(&words[..split]));
+                let atoms: &[QuantizedAtom] = bytemuck::cast_slice(&words[split..]);
+                Ok(Cow::Owned(dequantize_atoms(&bounds, atoms)))
+            }
+        }
+    }
+
+    /// Entrée du chunk géométrie, pour un upload en flux via `stream_chunk`
+    pub fn geometry_chunk(&self) -> Result<&ChunkEntry, SeedError> {
+        self.chunk(ChunkKind::Geometry)
+            .ok_or(SeedError::MissingChunk(ChunkKind::Geometry))
+    }
+
+    /// Matériaux (vide si le bake n'en contient pas)
+    pub fn materials(&self) -> Result<Cow<'_, [MaterialData]>, SeedError> {
+        self.pod_chunk(ChunkKind::Materials)
+    }
+
+    /// Nœuds du LBVH (vide si le bake n'en contient pas), racine en 0
+    pub fn bvh(&self) -> Result<Cow<'_, [BvhNode]>, SeedError> {
+        self.pod_chunk(ChunkKind::Bvh)
+    }
+
+    /// Objets nommés (vide si le bake n'en contient pas)
+    pub fn objects(&self) -> Result<Cow<'_, [SeedObject]>, SeedError> {
+        self.pod_chunk(ChunkKind::Objects)
+    }
+
+    /// Fonction de clé du tri spatial (absente des bakes sans tri)
+    pub fn ordering(&self) -> Result<Option<OrderingInfo>, SeedError> {
+        let Some(entry) = self.chunk(ChunkKind::Ordering) else {
+            return Ok(None);
+        };
+        let bytes = self.chunk_bytes(entry);
+        if bytes.len() != std::mem::size_of::<OrderingInfo>() {
+            return Err(SeedError::SizeMismatch {
+                kind: entry.kind,
+                expected: std::mem::size_of::<OrderingInfo>() as u64,
+                actual: entry.size,
+            });
+        }
+        Ok(Some(bytemuck::pod_read_unaligned(bytes)))
+    }
+
+    /// Origine monde double précision (sources géoréférencées), None sinon
+    pub fn origin(&self) -> Result<Option<WorldPos>, SeedError> {
+        Ok(self.pod_chunk::<WorldOrigin>(ChunkKind::Origin)?.first().map(WorldOrigin::position))
+    }
+
+    /// Métadonnées archivées, validées par rkyv mais jamais désérialisées
+    pub fn metadata(&self) -> Result<Option<&ArchivedSeedMetadata>, SeedError> {
+        let Some(entry) = self.chunk(ChunkKind::Metadata) else {
+            return Ok(None);
+        };
+        rkyv::check_archived_root::<SeedMetadata>(self.chunk_bytes(entry))
+            .map(Some)
+            .map_err(|_| SeedError::InvalidArchive { kind: entry.kind })
+    }
+
+    /// Flux d'attributs utilisateur (chunks ATTR)
+    pub fn attributes(&self) -> Result<Vec<AttributeStream<'_>>, SeedError> {
+        self.chunks.iter()
+            .filter(|e| e.kind() == Some(ChunkKind::Attributes))
+            .map(|entry| self.decode_attribute(entry))
+            .collect()
+    }
+
+    /// Flux d'attribut `name`. Seuls les en-têtes des autres chunks ATTR sont lus :
+    /// un gros flux compressé (`splat_sh`...) n'est décodé que s'il est demandé.
+    pub fn attribute(&self, name: &str) -> Result<Option<AttributeStream<'_>>, SeedError> {
+        for entry in self.chunks.iter().filter(|e| e.kind() == Some(ChunkKind::Attributes)) {
+            if self.attribute_header(entry)?.name() == name {
+                return self.decode_attribute(entry).map(Some);
+            }
+        }
+        Ok(None)
+    }
+
+    /// `AttributeHeader` en tête d'un chunk ATTR, sans décoder les valeurs
+    fn attribute_header(&self, entry: &ChunkEntry) -> Result<AttributeHeader, SeedError> {
+        let mut head = [0u8; std::mem::size_of::<AttributeHeader>()];
+        let mut stream = self.chunk_stream(entry)?;
+        let mut filled = 0;
+        while filled < head.len() {
+            match stream.read(&mut head[filled..])? {
+                0 => return Err(SeedError::SizeMismatch { kind: entry.kind, expected: head.len() as u64, actual: filled as u64 }),
+                n => filled += n,
+            }
+        }
+        Ok(bytemuck::pod_read_unaligned(&head))
+    }
+
+    /// Un chunk ATTR décodé : en-tête et `count * components` valeurs
+    fn decode_attribute(&self, entry: &ChunkEntry) -> Result<AttributeStream<'_>, SeedError> {
+        let head_size = std::mem::size_of::<AttributeHeader>();
+        let head_floats = head_size / std::mem::size_of::<f32>();
+        let payload = self.decode_pod::<f32>(entry)?;
+        if payload.len() < head_floats {
+            return Err(SeedError::SizeMismatch { kind: entry.kind, expected: head_size as u64, actual: entry.raw_size });
+        }
+        let header: AttributeHeader =
+            bytemuck::pod_read_unaligned(bytemuck::cast_slice(&payload[..head_floats]));
+        let len = header.count.saturating_mul(header.components as u64) as usize;
+        if payload.len() - head_floats < len {
+            return Err(SeedError::SizeMismatch {
+                kind: entry.kind,
+                expected: (head_size + len * 4) as u64,
+                actual: entry.raw_size,
+            });
+        }
+        let data = match payload {
+            Cow::Borrowed(all) => Cow::Borrowed(&all[head_floats..head_floats + len]),
+            Cow::Owned(mut all) => {
+                all.truncate(head_floats + len);
+                all.drain(..head_floats);
+                Cow::Owned(all)
+            }
+        };
+        Ok((header, data))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::format::SeedWriter;
+
+    fn atoms(count: usize) -> Vec<[f32; 6]> {
+        (0..count).map(|i| {
+            let t = i as f32 * 0.1;
+            [t.sin(), t.cos(), t, 0.0, 0.0, 1.0]
+        }).collect()
+    }
+
+    fn bake(compression: Option<i32>) -> Vec<u8> {
+        let geometry = atoms(1000);
+        let color: Vec<f32> = (0..geometry.len() * 3).map(|i| (i % 7) as f32 / 7.0).collect();
+        let roughness = vec![0.5f32; geometry.len()];
+        let objects = [
+            SeedObject::new("a", 0, 600, 0, [-1.0; 3], [1.0; 3]),
+            SeedObject::new("b", 600, 400, 0, [-1.0; 3], [1.0; 3]),
+        ];
+        let mut writer = SeedWriter::new(geometry.len() as u64);
+        writer
+            .set_compression(compression)
+            .set_content_hash([7; 32])
+            .add_pod_chunk(ChunkKind::Geometry, &geometry)
+            .add_pod_chunk(ChunkKind::Objects, &objects)
+            .add_pod_chunk(ChunkKind::Origin, &[WorldOrigin::new(WorldPos::new(650_000.0, 6_860_000.0, 12.5))])
+            .add_attribute("color", 3, &color)
+            .add_attribute("roughness", 1, &roughness)
+            .add_metadata(&SeedMetadata { generator: "test".to_string(), source: "a.obj".to_string() });
+        let mut bytes = Vec::new();
+        writer.write(&mut bytes).unwrap();
+        bytes
+    }
+
+    #[test]
+    fn writer_reader_round_trip() {
+        for compression in [None, Some(3)] {
+            let bytes = bake(compression);
+            let reader = SeedReader::from_bytes(&bytes).unwrap();
+            reader.verify_checksums().unwrap();
+            assert_eq!(reader.content_hash(), [7; 32]);
+            assert_eq!(reader.geometry().unwrap().as_ref(), atoms(1000).as_slice());
+
+            let objects = reader.objects().unwrap();
+            assert_eq!(objects.iter().map(SeedObject::name).collect::<Vec<_>>(), ["a", "b"]);
+            assert_eq!(reader.origin().unwrap(), Some(WorldPos::new(650_000.0, 6_860_000.0, 12.5)));
+            assert_eq!(reader.metadata().unwrap().unwrap().source.as_str(), "a.obj");
+
+            let names: Vec<String> = reader.attributes().unwrap().iter().map(|(h, _)| h.name().to_string()).collect();
+            assert_eq!(names, ["color", "roughness"]);
+            let (header, roughness) = reader.attribute("roughness").unwrap().unwrap();
+            assert_eq!((header.components, header.count), (1, 1000));
+            assert!(roughness.iter().all(|&r| r == 0.5));
+            assert!(reader.attribute("metallic").unwrap().is_none());
+        }
+        // La compression n'est gardée que si elle gagne
+        assert!(bake(Some(3)).len() < bake(None).len());
+    }
+
+    #[test]
+    fn rejects_damaged_files() {
+        let bytes = bake(Some(3));
+        assert!(matches!(SeedReader::from_bytes(&bytes[..16]), Err(SeedError::Truncated { .. })));
+
+        let mut bad_magic = bytes.clone();
+        bad_magic[0] ^= 0xff;
+        assert!(matches!(SeedReader::from_bytes(&bad_magic), Err(SeedError::BadMagic(_))));
+
+        // Dernier octet d'un payload : ouverture valide, checksum faux
+        let mut flipped = bytes.clone();
+        *flipped.last_mut().unwrap() ^= 0xff;
+        let reader = SeedReader::from_bytes(&flipped).unwrap();
+        assert!(matches!(reader.verify_checksums(), Err(SeedError::ChecksumMismatch { .. })));
+
+        assert!(SeedReader::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+    }
+}
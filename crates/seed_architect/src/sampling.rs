@@ -0,0 +1,148 @@
+// crates/seed_architect/src/sampling.rs
+//
+// Génération d'atomes sur la surface des triangles, à densité cible.
+// La densité ne dépend plus de la tessellation : un grand triangle plat
+// reçoit autant d'atomes par unité² qu'une zone finement maillée.
+
+use glam::Vec3;
+use log::warn;
+use rand::Rng;
+use std::collections::HashMap;
+
+/// Stratégie de génération des atomes
+#[derive(Clone, Copy, Debug, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub enum SamplingMode {
+    /// Un atome par sommet triangulé (comportement historique)
+    #[default]
+    Vertices,
+    /// Tirage uniforme pondéré par l'aire, `density` atomes par unité²
+    Uniform { density: f32 },
+    /// Bruit bleu (Poisson-disk) : même densité, espacement minimal 0.7/√densité
+    /// (complété par tirage uniforme si la surface ne le permet pas)
+    PoissonDisk { density: f32 },
+}
+
+/// Rayon Poisson-disk × √densité. L'empilement hexagonal d'espacement r porte
+/// 2/(√3·r²) points par unité² ; le dart throwing sature vers 55 % de cette
+/// limite, soit ~0.63/r² : r = 0.7/√densité laisse ~30 % de marge sur la cible.
+const POISSON_RADIUS: f32 = 0.7;
+
+/// Triangle avec normales par sommet, extrait du flux `[f32; 6]`
+struct Triangle {
+    p: [Vec3; 3],
+    n: [Vec3; 3],
+}
+
+impl Triangle {
+    fn area(&self) -> f32 {
+        (self.p[1] - self.p[0]).cross(self.p[2] - self.p[0]).length() * 0.5
+    }
+
+    /// Point uniforme sur le triangle (racine carrée de Turk) + normale interpolée
+    fn sample(&self, rng: &mut impl Rng) -> [f32; 6] {
+        let r1 = rng.random::<f32>().sqrt();
+        let r2 = rng.random::<f32>();
+        let (u, v, w) = (1.0 - r1, r1 * (1.0 - r2), r1 * r2);
+
+        let p = self.p[0] * u + self.p[1] * v + self.p[2] * w;
+        let mut n = (self.n[0] * u + self.n[1] * v + self.n[2] * w).normalize_or_zero();
+        if n == Vec3::ZERO {
+            n = (self.p[1] - self.p[0]).cross(self.p[2] - self.p[0]).normalize_or_zero();
+        }
+        [p.x, p.y, p.z, n.x, n.y, n.z]
+    }
+}
+
+fn triangles(vertices: &[[f32; 6]]) -> Vec<Triangle> {
+    vertices.chunks_exact(3).map(|tri| Triangle {
+        p: [0, 1, 2].map(|k| Vec3::new(tri[k][0], tri[k][1], tri[k][2])),
+        n: [0, 1, 2].map(|k| Vec3::new(tri[k][3], tri[k][4], tri[k][5])),
+    }).collect()
+}
+
+/// Atomes générés + triangle source de chacun (matériau, UV...)
+pub type SurfaceSamples = (Vec<[f32; 6]>, Vec<u32>);
+
+/// Échantillonne `round(aire * density)` atomes sur une soupe de triangles
+/// (3 sommets consécutifs = 1 triangle).
+pub fn sample_surface<R: Rng>(vertices: &[[f32; 6]], mode: SamplingMode, rng: &mut R) -> SurfaceSamples {
+    let density = match mode {
+        SamplingMode::Vertices => {
+            let sources = (0..vertices.len() as u32).map(|i| i / 3).collect();
+            return (vertices.to_vec(), sources);
+        }
+        SamplingMode::Uniform { density } | SamplingMode::PoissonDisk { density } => density,
+    };
+
+    let tris = triangles(vertices);
+    let total_area: f32 = tris.iter().map(Triangle::area).sum();
+    let target = (total_area * density).round() as usize;
+    if target == 0 || tris.is_empty() {
+        return (Vec::new(), Vec::new());
+    }
+
+    // Aires cumulées -> choix du triangle par recherche dichotomique
+    let cdf: Vec<f32> = tris.iter()
+        .scan(0.0, |acc, t| { *acc += t.area(); Some(*acc) })
+        .collect();
+    let draw = |rng: &mut R| {
+        let r = rng.random::<f32>() * total_area;
+        let idx = cdf.partition_point(|&c| c < r).min(tris.len() - 1);
+        (tris[idx].sample(rng), idx as u32)
+    };
+
+    match mode {
+        SamplingMode::PoissonDisk { .. } => {
+            // Dart throwing sur un pool de candidats, rayon sous la saturation
+            let radius = POISSON_RADIUS / density.sqrt();
+            let candidates: Vec<([f32; 6], u32)> = (0..target * 8).map(|_| draw(rng)).collect();
+            let (mut atoms, mut sources) = poisson_eliminate(candidates, radius, target);
+            if atoms.len() < target {
+                // Surface trop morcelée (triangles plus petits que le rayon) : la densité
+                // prime sur l'espacement, le reste est tiré uniformément
+                warn!("⚠️ Poisson-disk : {} atomes sur {}, complétés par tirage uniforme", atoms.len(), target);
+                let (extra, extra_sources): SurfaceSamples = (atoms.len()..target).map(|_| draw(rng)).unzip();
+                atoms.extend(extra);
+                sources.extend(extra_sources);
+            }
+            (atoms, sources)
+        }
+        _ => (0..target).map(|_| draw(rng)).unzip(),
+    }
+}
+
+/// Garde les candidats (dans leur ordre aléatoire) tant qu'aucun atome
+/// accepté n'est à moins de `radius`. Grille de hachage de cellule `radius`.
+fn poisson_eliminate(candidates: Vec<([f32; 6], u32)>, radius: f32, target: usize) -> SurfaceSamples {
+    let cell = |p: Vec3| (p / radius).floor().as_ivec3();
+    let mut grid: HashMap<glam::IVec3, Vec<Vec3>> = HashMap::new();
+    let mut accepted = Vec::with_capacity(target);
+    let mut sources = Vec::with_capacity(target);
+    let radius_sq = radius * radius;
+
+    for (atom, source) in candidates {
+        let p = Vec3::new(atom[0], atom[1], atom[2]);
+        let c = cell(p);
+        let mut blocked = false;
+        'search: for dz in -1..=1 {
+            for dy in -1..=1 {
+                for dx in -1..=1 {
+                    let Some(points) = grid.get(&(c + glam::IVec3::new(dx, dy, dz))) else { continue };
+                    if points.iter().any(|q| q.distance_squared(p) < radius_sq) {
+                        blocked = true;
+                        break 'search;
+                    }
+                }
+            }
+        }
+        if !blocked {
+            grid.entry(c).or_default().push(p);
+            accepted.push(atom);
+            sources.push(source);
+            if accepted.len() == target {
+                break;
+            }
+        }
+    }
+    (accepted, sources)
+}
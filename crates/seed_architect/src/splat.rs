@@ -0,0 +1,108 @@
+// crates/seed_architect/src/splat.rs
+//
+// Scènes Gaussian Splatting (3DGS) : le `point_cloud.ply` produit par
+// l'entraînement (INRIA et dérivés). Chaque sommet est une gaussienne :
+//   x/y/z, scale_0..2 (log), rot_0..3 (quaternion wxyz non normalisé),
+//   opacity (logit), f_dc_0..2 + f_rest_* (harmoniques sphériques, canal par canal)
+//
+// On stocke des valeurs prêtes pour le shader (exp, sigmoïde, quaternion
+// normalisé xyzw, SH entrelacées RGB par coefficient) et le header est marqué
+// SEED_FLAG_SPLATS. La normale de l'atome est le plus petit axe de la gaussienne
+// et "color" sa couleur de degré 0 : le pipeline de sprites reste utilisable.
+
+use crate::importer::{RawScene, COLOR_ATTRIBUTE};
+use crate::ply::PlyCloud;
+use crate::texture::srgb_to_linear;
+use glam::{Quat, Vec3};
+use log::{info, warn};
+
+/// Attributs ATTR d'une scène de gaussiennes
+pub const SPLAT_SCALE_ATTRIBUTE: &str = "splat_scale"; // 3 : écarts-types, unités monde
+pub const SPLAT_ROTATION_ATTRIBUTE: &str = "splat_rotation"; // 4 : quaternion xyzw normalisé
+pub const SPLAT_OPACITY_ATTRIBUTE: &str = "splat_opacity"; // 1 : 0..1
+pub const SPLAT_SH_ATTRIBUTE: &str = "splat_sh"; // 3 × (degré + 1)² : RGB par coefficient
+
+/// Harmonique de degré 0 : 1 / (2 sqrt(pi))
+pub const SH_C0: f32 = 0.282_094_8;
+
+/// Propriétés obligatoires d'un PLY 3DGS (f_rest_* optionnelles : degré 0 seul)
+const GAUSSIAN_PROPERTIES: [&str; 11] = [
+    "f_dc_0", "f_dc_1", "f_dc_2", "opacity", "scale_0", "scale_1", "scale_2", "rot_0", "rot_1", "rot_2", "rot_3",
+];
+
+fn column<'a>(cloud: &'a PlyCloud, name: &str) -> Option<&'a [f32]> {
+    cloud.scalars.iter().find(|(n, _)| n == name).map(|(_, values)| values.as_slice())
+}
+
+/// Le nuage porte-t-il des gaussiennes plutôt que des points mesurés ?
+pub fn is_gaussian(cloud: &PlyCloud) -> bool {
+    GAUSSIAN_PROPERTIES.iter().all(|name| column(cloud, name).is_some())
+}
+
+/// Gaussiennes -> un objet "splats", attributs `splat_*` et couleur de degré 0
+pub(crate) fn load_raw_splats(cloud: PlyCloud) -> RawScene {
+    let required = |name: &str| column(&cloud, name).unwrap_or_else(|| panic!("❌ Propriété 3DGS `{}` absente", name));
+    let [dc_r, dc_g, dc_b] = ["f_dc_0", "f_dc_1", "f_dc_2"].map(required);
+    let [sx, sy, sz] = ["scale_0", "scale_1", "scale_2"].map(required);
+    let [qw, qx, qy, qz] = ["rot_0", "rot_1", "rot_2", "rot_3"].map(required);
+    let opacity = required("opacity");
+
+    // f_rest_* : (K - 1) coefficients du rouge, puis du vert, puis du bleu
+    let mut rest: Vec<&[f32]> = (0..).map_while(|k| column(&cloud, &format!("f_rest_{}", k))).collect();
+    if !rest.len().is_multiple_of(3) {
+        warn!("⚠️ {} coefficients f_rest_* (pas un multiple de 3) : degré 0 seul", rest.len());
+        rest.clear();
+    }
+    let per_channel = rest.len() / 3;
+    let coefficients = per_channel + 1;
+    info!("✨ {} gaussiennes, {} coefficients SH par canal", cloud.positions.len(), coefficients);
+
+    let count = cloud.positions.len();
+    let mut points = Vec::with_capacity(count);
+    let mut colors = Vec::with_capacity(count * 3);
+    let mut scales = Vec::with_capacity(count * 3);
+    let mut rotations = Vec::with_capacity(count * 4);
+    let mut opacities = Vec::with_capacity(count);
+    let mut sh = Vec::with_capacity(count * coefficients * 3);
+    for (i, p) in cloud.positions.iter().enumerate() {
+        let scale = Vec3::new(sx[i], sy[i], sz[i]).exp();
+        let q = Quat::from_xyzw(qx[i], qy[i], qz[i], qw[i]);
+        let rotation = if q.length_squared() > 0.0 { q.normalize() } else { Quat::IDENTITY };
+
+        // Normale : axe le plus court de l'ellipsoïde (une gaussienne plate est une surface)
+        let axis = if scale.x <= scale.y && scale.x <= scale.z {
+            Vec3::X
+        } else if scale.y <= scale.z {
+            Vec3::Y
+        } else {
+            Vec3::Z
+        };
+        let n = rotation * axis;
+        points.push([p.x, p.y, p.z, n.x, n.y, n.z]);
+
+        let dc = Vec3::new(dc_r[i], dc_g[i], dc_b[i]);
+        // Degré 0 en espace d'affichage (sRGB) : "color" est linéaire
+        colors.extend((dc * SH_C0 + 0.5).clamp(Vec3::ZERO, Vec3::ONE).to_array().map(srgb_to_linear));
+        scales.extend_from_slice(&scale.to_array());
+        rotations.extend_from_slice(&rotation.to_array());
+        opacities.push(1.0 / (1.0 + (-opacity[i]).exp()));
+
+        sh.extend_from_slice(&dc.to_array());
+        for k in 0..per_channel {
+            sh.extend([rest[k][i], rest[per_channel + k][i], rest[2 * per_channel + k][i]]);
+        }
+    }
+
+    let mut raw = RawScene {
+        points,
+        objects: vec![("splats".to_string(), 0)],
+        splats: true,
+        ..Default::default()
+    };
+    raw.point_attributes.push((COLOR_ATTRIBUTE.to_string(), 3, colors));
+    raw.point_attributes.push((SPLAT_SCALE_ATTRIBUTE.to_string(), 3, scales));
+    raw.point_attributes.push((SPLAT_ROTATION_ATTRIBUTE.to_string(), 4, rotations));
+    raw.point_attributes.push((SPLAT_OPACITY_ATTRIBUTE.to_string(), 1, opacities));
+    raw.point_attributes.push((SPLAT_SH_ATTRIBUTE.to_string(), coefficients as u32 * 3, sh));
+    raw
+}
@@ -0,0 +1,172 @@
+// crates/seed_architect/src/texture.rs
+//
+// Textures MTL / glTF échantillonnées au bake : chaque atome reçoit la valeur
+// de la texture à son UV interpolé (attributs ATTR "color", "roughness",
+// "metallic"). Le runtime n'a jamais besoin des images.
+//
+// Convention des couleurs : l'attribut "color" et `MaterialData::base_color`
+// sont en RGB linéaire, quel que soit l'importeur. Ce qui est encodé en sRGB
+// (cartes de couleur, Kd des MTL, couleurs PLY / LAS...) est décodé une seule
+// fois au bake ; surface.glsl les utilise tels quels et seul `export_ply`
+// ré-encode en sRGB. Les cartes de données (rugosité, métal) restent brutes.
+
+use glam::{Vec2, Vec3};
+use log::warn;
+use std::path::{Path, PathBuf};
+
+/// Cartes MTL prises en charge : couleur, rugosité, métal
+pub const TEXTURE_KEYS: [&str; 3] = ["map_Kd", "map_Pr", "map_Pm"];
+
+/// Image décodée en RGBA flottant (valeurs brutes 0..1, voir `into_linear`)
+pub struct Texture {
+    width: u32,
+    height: u32,
+    texels: Vec<[f32; 4]>,
+}
+
+impl Texture {
+    /// Image déjà décodée (texels ligne par ligne, en partant du haut)
+    pub fn new(width: u32, height: u32, texels: Vec<[f32; 4]>) -> Self {
+        assert_eq!(texels.len(), (width * height) as usize, "❌ Texture : taille des texels incohérente");
+        Self { width, height, texels }
+    }
+
+    /// PNG / JPEG / TGA. Une texture illisible est ignorée (avertissement).
+    pub fn load(path: &Path) -> Option<Self> {
+        match image::open(path) {
+            Ok(img) => Some(Self::from_image(img)),
+            Err(e) => {
+                warn!("⚠️ Texture {} ignorée : {}", path.display(), e);
+                None
+            }
+        }
+    }
+
+    /// Image embarquée encodée (PNG / JPEG / TGA en mémoire), `label` pour les logs
+    pub fn decode(bytes: &[u8], label: &str) -> Option<Self> {
+        match image::load_from_memory(bytes) {
+            Ok(img) => Some(Self::from_image(img)),
+            Err(e) => {
+                warn!("⚠️ Texture embarquée {} ignorée : {}", label, e);
+                None
+            }
+        }
+    }
+
+    fn from_image(img: image::DynamicImage) -> Self {
+        let rgba = img.to_rgba32f();
+        Self {
+            width: rgba.width(),
+            height: rgba.height(),
+            texels: rgba.pixels().map(|p| p.0).collect(),
+        }
+    }
+
+    /// Décodage sRGB -> linéaire des canaux RGB (l'alpha est déjà linéaire)
+    pub fn into_linear(mut self) -> Self {
+        for texel in &mut self.texels {
+            for c in &mut texel[..3] {
+                *c = srgb_to_linear(*c);
+            }
+        }
+        self
+    }
+
+    fn texel(&self, x: i64, y: i64) -> [f32; 4] {
+        let x = x.rem_euclid(self.width as i64) as usize;
+        let y = y.rem_euclid(self.height as i64) as usize;
+        self.texels[y * self.width as usize + x]
+    }
+
+    /// Filtrage bilinéaire, UV répétés, V vers le haut (convention OBJ)
+    pub fn sample(&self, uv: Vec2) -> [f32; 4] {
+        let x = uv.x * self.width as f32 - 0.5;
+        let y = (1.0 - uv.y) * self.height as f32 - 0.5;
+        let (x0, y0) = (x.floor(), y.floor());
+        let (fx, fy) = (x - x0, y - y0);
+        let (x0, y0) = (x0 as i64, y0 as i64);
+
+        let corners = [
+            (self.texel(x0, y0), (1.0 - fx) * (1.0 - fy)),
+            (self.texel(x0 + 1, y0), fx * (1.0 - fy)),
+            (self.texel(x0, y0 + 1), (1.0 - fx) * fy),
+            (self.texel(x0 + 1, y0 + 1), fx * fy),
+        ];
+        let mut out = [0.0; 4];
+        for (texel, weight) in corners {
+            for c in 0..4 {
+                out[c] += texel[c] * weight;
+            }
+        }
+        out
+    }
+}
+
+/// Textures d'un matériau (chacune optionnelle)
+#[derive(Default)]
+pub struct MaterialTextures {
+    pub diffuse: Option<Texture>,
+    pub roughness: Option<Texture>,
+    pub metallic: Option<Texture>,
+}
+
+impl MaterialTextures {
+    /// `dir` = dossier des fichiers MTL (chemins relatifs)
+    pub fn load(m: &tobj::Material, dir: &Path) -> Self {
+        let load = |map: Option<&String>| map.and_then(|v| texture_path(v, dir)).and_then(|p| Texture::load(&p));
+        Self {
+            diffuse: load(m.diffuse_texture.as_ref()).map(Texture::into_linear),
+            roughness: load(m.unknown_param.get("map_Pr")),
+            metallic: load(m.unknown_param.get("map_Pm")),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.diffuse.is_none() && self.roughness.is_none() && self.metallic.is_none()
+    }
+}
+
+/// Fonction de transfert sRGB inverse (IEC 61966-2-1)
+pub fn srgb_to_linear(c: f32) -> f32 {
+    if c <= 0.04045 {
+        c / 12.92
+    } else {
+        ((c + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+/// Fonction de transfert sRGB (inverse de `srgb_to_linear`)
+pub fn linear_to_srgb(c: f32) -> f32 {
+    if c <= 0.0031308 {
+        c * 12.92
+    } else {
+        1.055 * c.powf(1.0 / 2.4) - 0.055
+    }
+}
+
+/// Chemin d'une carte MTL : dernier mot (les options `-s`, `-bm`... précèdent le fichier)
+pub fn texture_path(value: &str, dir: &Path) -> Option<PathBuf> {
+    value.split_whitespace().last().map(|file| dir.join(file))
+}
+
+/// UV du point de `tri` le plus proche de `p`
+pub fn interpolate_uv(p: Vec3, tri: [Vec3; 3], uvs: [Vec2; 3]) -> Vec2 {
+    let [u, v, w] = barycentric(p, tri);
+    uvs[0] * u + uvs[1] * v + uvs[2] * w
+}
+
+/// Coordonnées barycentriques de `p` dans `tri`, bornées au triangle
+pub fn barycentric(p: Vec3, tri: [Vec3; 3]) -> [f32; 3] {
+    let (e0, e1, d) = (tri[1] - tri[0], tri[2] - tri[0], p - tri[0]);
+    let (d00, d01, d11) = (e0.dot(e0), e0.dot(e1), e1.dot(e1));
+    let denom = d00 * d11 - d01 * d01;
+    // Seuil relatif : denom = |e0|²|e1|² sin²θ, un petit triangle n'est pas dégénéré
+    if denom <= 1e-12 * d00 * d11 {
+        return [1.0, 0.0, 0.0]; // Triangle dégénéré
+    }
+    let v = (d11 * d.dot(e0) - d01 * d.dot(e1)) / denom;
+    let w = (d00 * d.dot(e1) - d01 * d.dot(e0)) / denom;
+    let (v, w) = (v.clamp(0.0, 1.0), w.clamp(0.0, 1.0));
+    let (v, w) = if v + w > 1.0 { (v / (v + w), w / (v + w)) } else { (v, w) };
+    [1.0 - v - w, v, w]
+}
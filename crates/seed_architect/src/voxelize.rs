@@ -0,0 +1,127 @@
+// crates/seed_architect/src/voxelize.rs
+//
+// Remplissage volumique d'un maillage fermé : les centres de voxels situés à
+// l'intérieur deviennent des atomes (coupes, destructions...).
+//
+// Classification par nombre d'enroulement le long de rayons +X : chaque
+// triangle traversé compte +1 ou -1 selon le sens de sa normale. Contrairement
+// à la parité simple, des faces doublées ou imbriquées restent cohérentes.
+
+use crate::sampling::SurfaceSamples;
+use glam::{Vec2, Vec3};
+
+/// Décalage irrationnel des rayons pour ne jamais passer exactement par une arête
+const RAY_OFFSET: Vec2 = Vec2::new(1.0e-4 * std::f32::consts::SQRT_2, 1.0e-4 * std::f32::consts::E);
+
+/// Atomes intérieurs d'une soupe de triangles (3 sommets consécutifs = 1 triangle).
+/// `resolution` = nombre de voxels sur le plus grand axe de l'AABB.
+/// La normale d'un atome intérieur pointe du centre de l'objet vers l'atome.
+/// Chaque atome hérite du dernier triangle traversé par son rayon (matériau).
+pub fn fill_interior(vertices: &[[f32; 6]], resolution: u32) -> SurfaceSamples {
+    let tris: Vec<[Vec3; 3]> = vertices.chunks_exact(3)
+        .map(|t| [0, 1, 2].map(|k| Vec3::new(t[k][0], t[k][1], t[k][2])))
+        .collect();
+    if tris.is_empty() || resolution == 0 {
+        return (Vec::new(), Vec::new());
+    }
+
+    let (min, max) = tris.iter().flatten().fold(
+        (Vec3::splat(f32::MAX), Vec3::splat(f32::MIN)),
+        |(lo, hi), p| (lo.min(*p), hi.max(*p)),
+    );
+    let voxel = (max - min).max_element() / resolution as f32;
+    if voxel <= 0.0 {
+        return (Vec::new(), Vec::new());
+    }
+    let dims = ((max - min) / voxel).ceil().as_uvec3().max(glam::UVec3::ONE);
+    let center = (min + max) * 0.5;
+
+    // 1. Pour chaque colonne (y, z) : abscisses des croisements, signe et triangle
+    let column_count = dims.y as usize * dims.z as usize;
+    let column_index = |iy: u32, iz: u32| iz as usize * dims.y as usize + iy as usize;
+    let mut hits: Vec<Vec<(f32, i32, u32)>> = vec![Vec::new(); column_count];
+    let column_center = |iy: u32, iz: u32| {
+        Vec2::new(min.y + (iy as f32 + 0.5) * voxel, min.z + (iz as f32 + 0.5) * voxel) + RAY_OFFSET
+    };
+
+    for (tri, [a, b, c]) in tris.iter().enumerate() {
+        let lo = a.min(*b).min(*c);
+        let hi = a.max(*b).max(*c);
+        // Colonnes dont le rayon (décalé de RAY_OFFSET) tombe dans l'AABB YZ du triangle,
+        // élargies d'une colonne : `ray_x_intersection` tranche aux arrondis près
+        let first = (Vec2::new(lo.y, lo.z) - Vec2::new(min.y, min.z) - RAY_OFFSET) / voxel - 0.5;
+        let last = (Vec2::new(hi.y, hi.z) - Vec2::new(min.y, min.z) - RAY_OFFSET) / voxel - 0.5;
+        let y0 = (first.x.ceil() - 1.0).max(0.0) as u32;
+        let y1 = (last.x.floor() as i64 + 1).min(dims.y as i64 - 1);
+        let z0 = (first.y.ceil() - 1.0).max(0.0) as u32;
+        let z1 = (last.y.floor() as i64 + 1).min(dims.z as i64 - 1);
+        if y1 < 0 || z1 < 0 {
+            continue;
+        }
+
+        let facing = (*b - *a).cross(*c - *a).x;
+        if facing == 0.0 {
+            continue; // Triangle parallèle aux rayons
+        }
+        let sign = if facing > 0.0 { 1 } else { -1 };
+
+        for iz in z0..=z1 as u32 {
+            for iy in y0..=y1 as u32 {
+                let q = column_center(iy, iz);
+                if let Some(x) = ray_x_intersection(q, a, b, c) {
+                    hits[column_index(iy, iz)].push((x, sign, tri as u32));
+                }
+            }
+        }
+    }
+
+    // 2. Parcours des colonnes : enroulement cumulé au centre de chaque voxel
+    let mut atoms = Vec::new();
+    let mut sources = Vec::new();
+    for iz in 0..dims.z {
+        for iy in 0..dims.y {
+            let column = &mut hits[column_index(iy, iz)];
+            if column.is_empty() {
+                continue;
+            }
+            column.sort_by(|l, r| l.0.total_cmp(&r.0));
+            let q = column_center(iy, iz);
+
+            let mut winding = 0;
+            let mut next = 0;
+            for ix in 0..dims.x {
+                let x = min.x + (ix as f32 + 0.5) * voxel;
+                while next < column.len() && column[next].0 < x {
+                    winding += column[next].1;
+                    next += 1;
+                }
+                // Les voxels collés à la paroi sont déjà couverts par les atomes de surface
+                let near_wall = column.iter().any(|h| (h.0 - x).abs() < voxel * 0.25);
+                if winding != 0 && !near_wall {
+                    let p = Vec3::new(x, q.x, q.y);
+                    let n = (p - center).try_normalize().unwrap_or(Vec3::Y);
+                    atoms.push([p.x, p.y, p.z, n.x, n.y, n.z]);
+                    sources.push(column[next - 1].2);
+                }
+            }
+        }
+    }
+    (atoms, sources)
+}
+
+/// Abscisse où la droite (y, z) = q coupe le triangle, s'il la coupe
+fn ray_x_intersection(q: Vec2, a: &Vec3, b: &Vec3, c: &Vec3) -> Option<f32> {
+    // Coordonnées barycentriques dans le plan YZ
+    let (pa, pb, pc) = (Vec2::new(a.y, a.z), Vec2::new(b.y, b.z), Vec2::new(c.y, c.z));
+    let area = (pb - pa).perp_dot(pc - pa);
+    if area == 0.0 {
+        return None;
+    }
+    let w1 = (q - pa).perp_dot(pc - pa) / area;
+    let w2 = (pb - pa).perp_dot(q - pa) / area;
+    let w0 = 1.0 - w1 - w2;
+    if w0 < 0.0 || w1 < 0.0 || w2 < 0.0 {
+        return None;
+    }
+    Some(w0 * a.x + w1 * b.x + w2 * c.x)
+}